@@ -1,9 +1,14 @@
 //! Parsing attributes for `doc_impl` attribute macro.
 
+use crate::preset::Preset;
+use crate::template::Template;
 use syn::parse::{Parse, ParseStream, Result};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Error, Expr, ExprLit, ExprPath, Lit, MetaNameValue, Token};
+use syn::{
+    parse_quote, Attribute, Error, Expr, ExprLit, ExprPath, Ident, Lit, LitStr, Member, Meta, Path,
+    Token, Visibility,
+};
 
 // Helper functions
 
@@ -22,6 +27,41 @@ fn parse_option_usize(expr: Expr) -> Result<Option<usize>> {
     }
 }
 
+/// Parses `Join` from `Expr`.
+fn parse_join(expr: &Expr) -> Result<Join> {
+    match expr {
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("newline") => Ok(Join::Newline),
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("space") => Ok(Join::Space),
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("paragraph") => Ok(Join::Paragraph),
+        _ => Err(Error::new(
+            expr.span(),
+            "Expected `newline`, `space` or `paragraph`",
+        )),
+    }
+}
+
+/// Parses `OnConflict` from `Expr`.
+fn parse_on_conflict(expr: &Expr) -> Result<OnConflict> {
+    match expr {
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("keep") => Ok(OnConflict::Keep),
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("replace") => Ok(OnConflict::Replace),
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("error") => Ok(OnConflict::Error),
+        _ => Err(Error::new(
+            expr.span(),
+            "Expected `keep`, `replace` or `error`",
+        )),
+    }
+}
+
+/// Parses `Inherit` from `Expr`.
+fn parse_inherit(expr: &Expr) -> Result<Inherit> {
+    match expr {
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("none") => Ok(Inherit::None),
+        Expr::Path(ExprPath { path, .. }) if path.is_ident("field_type") => Ok(Inherit::FieldType),
+        _ => Err(Error::new(expr.span(), "Expected `none` or `field_type`")),
+    }
+}
+
 /// Parses `bool` from `Expr`.
 fn parse_bool(expr: Expr) -> Result<bool> {
     match expr {
@@ -44,8 +84,300 @@ fn parse_string(expr: &Expr) -> Result<String> {
     }
 }
 
+/// Parses a path from `Expr`, either bare or as a string literal.
+fn parse_path(expr: &Expr) -> Result<Path> {
+    match expr {
+        Expr::Path(ExprPath {
+            qself: None, path, ..
+        }) => Ok(path.clone()),
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit_str),
+            ..
+        }) => lit_str.parse(),
+        // Paths passed through `macro_rules` are wrapped in invisible groups
+        Expr::Group(group) => parse_path(&group.expr),
+        _ => Err(Error::new(expr.span(), "Expected path")),
+    }
+}
+
+/// Parses a visibility from a string literal in `Expr`, e.g. `"pub(crate)"`.
+fn parse_vis(expr: &Expr) -> Result<Visibility> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit_str),
+            ..
+        }) => lit_str.parse(),
+        _ => Err(Error::new(expr.span(), "Expected string literal")),
+    }
+}
+
+/// Parses the name of a generated function from `Expr`, mapping `false` to `None`.
+fn parse_fn_name(expr: &Expr) -> Result<Option<Ident>> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Bool(lit_bool),
+            ..
+        }) if !lit_bool.value => Ok(None),
+        Expr::Path(ExprPath { path, .. }) => path
+            .get_ident()
+            .cloned()
+            .map(Some)
+            .ok_or_else(|| Error::new(expr.span(), "Expected identifier")),
+        _ => Err(Error::new(expr.span(), "Expected identifier or `false`")),
+    }
+}
+
+/// Parses a `gen_attr` template from `Expr`.
+fn parse_template(expr: &Expr) -> Result<Template> {
+    let template = parse_string(expr)?;
+    Template::parse(&template).map_err(|msg| Error::new(expr.span(), msg))
+}
+
+/// Parses a preset from `Expr`.
+fn parse_preset(expr: &Expr) -> Result<Preset> {
+    let Expr::Lit(ExprLit {
+        lit: Lit::Str(lit), ..
+    }) = expr
+    else {
+        return Err(Error::new(expr.span(), "Expected string literal"));
+    };
+    Preset::from_name(&lit.value()).ok_or_else(|| {
+        Error::new(
+            lit.span(),
+            format!(
+                "Unknown preset: `{}`, expected one of {}",
+                lit.value(),
+                Preset::NAMES.join(", ")
+            ),
+        )
+    })
+}
+
+/// How lines of a documentation comment are joined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Join {
+    /// Join lines with `\n`, keeping the documentation comment as-is.
+    Newline,
+    /// Join non-empty lines with a single space, resulting in a single line.
+    Space,
+    /// Join lines of a paragraph with a single space, and paragraphs with an empty line.
+    Paragraph,
+}
+
+/// What to do when a generated attribute conflicts with an existing one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OnConflict {
+    /// Keep the existing attribute, and don't generate the conflicting one.
+    Keep,
+    /// Remove the conflicting options from the existing attribute, or the whole attribute if it has no options.
+    Replace,
+    /// Report an error on the existing attribute.
+    #[default]
+    Error,
+}
+
+/// Where undocumented fields get their documentation comments from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Inherit {
+    /// Nowhere, leaving them undocumented.
+    #[default]
+    None,
+    /// The types of the fields, if they implement `DocFor`.
+    FieldType,
+}
+
+/// How documentation comments are extracted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DocStyle {
+    /// The number of leading whitespace characters to strip from each line. `None` means all.
+    pub strip: Option<usize>,
+    /// How lines are joined.
+    pub join: Join,
+}
+
+impl Default for DocStyle {
+    fn default() -> Self {
+        Self {
+            strip: Some(0),
+            join: Join::Newline,
+        }
+    }
+}
+
+/// Parses the name of a field or variant, or the index of a tuple field, from `Member`.
+fn member_name(member: Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+/// Items that `gen_attr` can target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// The annotated type itself.
+    Container,
+    /// Fields of a struct or union.
+    Fields,
+    /// Variants of an enum.
+    Variants,
+    /// Fields of enum variants.
+    VariantFields,
+}
+
+impl Target {
+    /// Parses a target from `LitStr`.
+    fn parse_lit(lit: &LitStr) -> Result<Self> {
+        match lit.value().as_str() {
+            "container" => Ok(Self::Container),
+            "fields" => Ok(Self::Fields),
+            "variants" => Ok(Self::Variants),
+            "variant_fields" => Ok(Self::VariantFields),
+            other => Err(Error::new(
+                lit.span(),
+                format!("Unknown target: `{other}`, expected one of `container`, `fields`, `variants` or `variant_fields`"),
+            )),
+        }
+    }
+}
+
+/// An attribute to generate, as specified by `gen_attr`.
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub struct GenAttr {
+    /// The template of the attribute.
+    pub template: Template,
+    /// The items to generate the attribute for. Default is fields and variants.
+    pub targets: Vec<Target>,
+    /// If not empty, only generate the attribute for items with these names.
+    pub include: Vec<String>,
+    /// Do not generate the attribute for items with these names.
+    pub exclude: Vec<String>,
+    /// Overrides the `strip` option for this attribute.
+    #[allow(clippy::option_option, reason = "`Some(None)` means stripping all")]
+    pub strip: Option<Option<usize>>,
+    /// Overrides the `join` option for this attribute.
+    pub join: Option<Join>,
+    /// Overrides the `on_conflict` option for this attribute.
+    pub on_conflict: Option<OnConflict>,
+}
+
+impl GenAttr {
+    /// Creates a `GenAttr` with default options from a template.
+    pub fn new(template: Template) -> Self {
+        Self {
+            template,
+            targets: vec![Target::Fields, Target::Variants],
+            include: Vec::new(),
+            exclude: Vec::new(),
+            strip: None,
+            join: None,
+            on_conflict: None,
+        }
+    }
+
+    /// The style to extract documentation comments with, given the default style.
+    pub fn style(&self, default: DocStyle) -> DocStyle {
+        DocStyle {
+            strip: self.strip.unwrap_or(default.strip),
+            join: self.join.unwrap_or(default.join),
+        }
+    }
+
+    /// Whether the attribute should be generated for an item of the given target, identified by any of the given names.
+    pub fn applies_to(&self, target: Target, names: &[String]) -> bool {
+        self.targets.contains(&target)
+            && (self.include.is_empty() || names.iter().any(|n| self.include.contains(n)))
+            && !names.iter().any(|n| self.exclude.contains(n))
+    }
+}
+
+impl Parse for GenAttr {
+    /// Parses the list form, e.g. `gen_attr(target = "container", "command(about = {doc})")`.
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut template = None;
+        let mut targets = Vec::new();
+        let mut include = Vec::new();
+        let mut exclude = Vec::new();
+        let mut strip = None;
+        let mut join = None;
+        let mut on_conflict = None;
+
+        while !input.is_empty() {
+            if input.peek(LitStr) {
+                let lit: LitStr = input.parse()?;
+                if template.is_some() {
+                    return Err(Error::new(lit.span(), "Duplicate template"));
+                }
+                let parsed =
+                    Template::parse(&lit.value()).map_err(|msg| Error::new(lit.span(), msg))?;
+                template = Some(parsed);
+            } else {
+                let meta: Meta = input.parse()?;
+                let name = meta
+                    .path()
+                    .get_ident()
+                    .ok_or_else(|| Error::new(meta.span(), "Expected an identifier"))?;
+                match (name.to_string().as_str(), &meta) {
+                    ("target", Meta::NameValue(mnv)) => {
+                        let Expr::Lit(ExprLit {
+                            lit: Lit::Str(lit), ..
+                        }) = &mnv.value
+                        else {
+                            return Err(Error::new(mnv.value.span(), "Expected string literal"));
+                        };
+                        targets.push(Target::parse_lit(lit)?);
+                    }
+                    ("strip", Meta::NameValue(mnv)) => {
+                        strip = Some(parse_option_usize(mnv.value.clone())?);
+                    }
+                    ("join", Meta::NameValue(mnv)) => {
+                        join = Some(parse_join(&mnv.value)?);
+                    }
+                    ("on_conflict", Meta::NameValue(mnv)) => {
+                        on_conflict = Some(parse_on_conflict(&mnv.value)?);
+                    }
+                    ("include" | "exclude", Meta::List(list)) => {
+                        let members = list
+                            .parse_args_with(Punctuated::<Member, Token![,]>::parse_terminated)?;
+                        let names = members.into_iter().map(member_name);
+                        if name == "include" {
+                            include.extend(names);
+                        } else {
+                            exclude.extend(names);
+                        }
+                    }
+                    _ => {
+                        return Err(Error::new(
+                            name.span(),
+                            format!("Unknown `gen_attr` option: `{name}`"),
+                        ));
+                    }
+                }
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        let template = template.ok_or_else(|| input.error("Expected a template string literal"))?;
+        let mut gen_attr = Self::new(template);
+        if !targets.is_empty() {
+            gen_attr.targets = targets;
+        }
+        gen_attr.include = include;
+        gen_attr.exclude = exclude;
+        gen_attr.strip = strip;
+        gen_attr.join = join;
+        gen_attr.on_conflict = on_conflict;
+        Ok(gen_attr)
+    }
+}
+
 /// Attributes for the `doc_impl` attribute macro.
-#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[allow(clippy::struct_excessive_bools, reason = "Independent options")]
 pub struct MacroAttrs {
     /// The number of leading whitespace characters to strip from the documentation comments.
     ///
@@ -53,62 +385,309 @@ pub struct MacroAttrs {
     ///
     /// When parsing, `all` is mapped to `None` and `n` to `Some(n)`.
     pub strip: Option<usize>,
+    /// How lines of the documentation comments are joined. Default is `Join::Newline`.
+    pub join: Join,
     /// Whether to generate implementation for `DocFor` and `doc_for_field`. Default is `true`.
     pub doc_for: bool,
     /// Whether to generate implementation for `DocDyn` for an enum. Default is `false`.
     pub doc_dyn: bool,
-    /// List of attributes to generate for each field. Default is empty.
-    pub gen_attrs: Vec<String>,
+    /// Whether to generate implementation for `FromDoc` for a unit-only enum. Default is `false`.
+    pub from_doc: bool,
+    /// List of attributes to generate. Default is empty.
+    pub gen_attrs: Vec<GenAttr>,
+    /// List of presets, expanding to attributes to generate. Default is empty.
+    pub presets: Vec<Preset>,
+    /// What to do when a generated attribute conflicts with an existing one. Default is `OnConflict::Error`.
+    pub on_conflict: OnConflict,
+    /// Path to the `doc_for` crate, used in generated code. Default is `::doc_for`.
+    pub crate_path: Path,
+    /// Name of the generated method for getting the documentation comment of a field or variant, or `None` to not generate it. Default is `doc_for_field`.
+    pub field_fn: Option<Ident>,
+    /// Name of the generated method for getting the documentation comment of a field or variant by its position, or `None` to not generate it. Default is `doc_for_index`.
+    pub index_fn: Option<Ident>,
+    /// Name of the generated methods for getting the documentation comment of a variant by its discriminant, or `None` to not generate them. Only generated for enums with a primitive representation and unit variants. Default is `doc_for_discriminant`.
+    pub discriminant_fn: Option<Ident>,
+    /// Visibility of generated methods and types, or `None` to use the visibility of the type. Default is `None`.
+    pub vis: Option<Visibility>,
+    /// Whether to generate an enum identifying the fields or variants. Default is `false`.
+    pub field_enum: bool,
+    /// Whether to forward to the type of the only field of a newtype. Default is `false`.
+    pub transparent: bool,
+    /// Where undocumented fields get their documentation comments from. Default is `Inherit::None`.
+    pub inherit: Inherit,
+    /// The foreign type that the annotated type mirrors, if any. Default is `None`.
+    pub remote: Option<Path>,
+    /// Whether to use the names from `#[serde(...)]` attributes and inline flattened fields. Default is `false`.
+    pub serde_names: bool,
+}
+
+impl MacroAttrs {
+    /// The style to extract documentation comments with.
+    pub const fn style(&self) -> DocStyle {
+        DocStyle {
+            strip: self.strip,
+            join: self.join,
+        }
+    }
 }
 
 impl Default for MacroAttrs {
     fn default() -> Self {
         Self {
             strip: Some(0),
+            join: Join::Newline,
             doc_for: true,
             doc_dyn: false,
+            from_doc: false,
             gen_attrs: Vec::new(),
+            presets: Vec::new(),
+            on_conflict: OnConflict::Error,
+            crate_path: parse_quote!(::doc_for),
+            field_fn: Some(parse_quote!(doc_for_field)),
+            index_fn: Some(parse_quote!(doc_for_index)),
+            discriminant_fn: Some(parse_quote!(doc_for_discriminant)),
+            vis: None,
+            field_enum: false,
+            transparent: false,
+            inherit: Inherit::None,
+            remote: None,
+            serde_names: false,
         }
     }
 }
 
+impl MacroAttrs {
+    /// Options that can't be used in derive mode, as they either mutate the item or select what to derive.
+    const ATTR_ONLY: [&str; 6] = [
+        "doc_for",
+        "doc_dyn",
+        "from_doc",
+        "gen_attr",
+        "preset",
+        "on_conflict",
+    ];
+
+    /// Applies a single option.
+    fn apply(&mut self, meta: Meta) -> Result<()> {
+        let name = meta
+            .path()
+            .get_ident()
+            .ok_or_else(|| Error::new(meta.span(), "Expected an identifier"))?
+            .clone();
+        let value = match meta {
+            Meta::Path(_) if name == "transparent" => {
+                self.transparent = true;
+                return Ok(());
+            }
+            Meta::Path(_) if name == "serde_names" => {
+                self.serde_names = true;
+                return Ok(());
+            }
+            Meta::NameValue(mnv) => mnv.value,
+            Meta::List(list) if name == "gen_attr" => {
+                self.gen_attrs.push(list.parse_args()?);
+                return Ok(());
+            }
+            _ => {
+                return Err(Error::new(meta.span(), format!("Expected `{name} = ...`")));
+            }
+        };
+        match name.to_string().as_str() {
+            "strip" => {
+                self.strip = parse_option_usize(value)?;
+            }
+            "join" => {
+                self.join = parse_join(&value)?;
+            }
+            "preset" => {
+                self.presets.push(parse_preset(&value)?);
+            }
+            "on_conflict" => {
+                self.on_conflict = parse_on_conflict(&value)?;
+            }
+            "crate" => {
+                self.crate_path = parse_path(&value)?;
+            }
+            "remote" => {
+                self.remote = Some(parse_path(&value)?);
+            }
+            "field_fn" => {
+                self.field_fn = parse_fn_name(&value)?;
+            }
+            "index_fn" => {
+                self.index_fn = parse_fn_name(&value)?;
+            }
+            "discriminant_fn" => {
+                self.discriminant_fn = parse_fn_name(&value)?;
+            }
+            "vis" => {
+                self.vis = Some(parse_vis(&value)?);
+            }
+            "field_enum" => {
+                self.field_enum = parse_bool(value)?;
+            }
+            "transparent" => {
+                self.transparent = parse_bool(value)?;
+            }
+            "serde_names" => {
+                self.serde_names = parse_bool(value)?;
+            }
+            "inherit" => {
+                self.inherit = parse_inherit(&value)?;
+            }
+            "doc_for" => {
+                self.doc_for = parse_bool(value)?;
+            }
+            "doc_dyn" => {
+                self.doc_dyn = parse_bool(value)?;
+            }
+            "from_doc" => {
+                self.from_doc = parse_bool(value)?;
+            }
+            "gen_attr" => {
+                self.gen_attrs.push(GenAttr::new(parse_template(&value)?));
+            }
+            _ => {
+                return Err(Error::new(
+                    name.span(),
+                    format!("Unknown attribute: `{name}`"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies options from `#[doc_for(...)]` helper attributes on the container. In derive mode, options that only make sense for `doc_impl` are rejected.
+    pub fn apply_helpers(&mut self, attrs: &[Attribute], derive: bool) -> Result<()> {
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("doc_for")) {
+            let parsed = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+            for meta in parsed {
+                if derive
+                    && let Some(name) = meta.path().get_ident()
+                    && Self::ATTR_ONLY.contains(&name.to_string().as_str())
+                {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("`{name}` is not supported in derive mode, use `#[doc_impl({name} = ...)]` instead"),
+                    ));
+                }
+                self.apply(meta)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl Parse for MacroAttrs {
     fn parse(input: ParseStream) -> Result<Self> {
-        let parsed: Punctuated<MetaNameValue, Token![,]> = Punctuated::parse_terminated(input)?;
+        let parsed: Punctuated<Meta, Token![,]> = Punctuated::parse_terminated(input)?;
         let mut attrs = Self::default();
 
-        for mnv in parsed {
-            let (name, value) = (
-                mnv.path
-                    .get_ident()
-                    .ok_or_else(|| Error::new(mnv.span(), "Expected an identifier"))?,
-                mnv.value,
-            );
-            match name.to_string().as_str() {
-                "strip" => {
-                    attrs.strip = parse_option_usize(value)?;
-                }
-                "doc_for" => {
-                    attrs.doc_for = parse_bool(value)?;
-                }
-                "doc_dyn" => {
-                    attrs.doc_dyn = parse_bool(value)?;
-                }
-                "gen_attr" => {
-                    attrs.gen_attrs.push(parse_string(&value)?);
-                }
-                _ => return Err(Error::new(name.span(), format!("Unknown attribute: `{name}`"))),
-            }
+        for meta in parsed {
+            attrs.apply(meta)?;
         }
 
         Ok(attrs)
     }
 }
 
+/// Options from `#[doc_for(...)]` helper attributes on a field or variant.
+#[derive(Default)]
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub struct ItemAttrs {
+    /// Whether to treat the item as undocumented and skip generating attributes for it.
+    pub skip: bool,
+    /// Overrides the documentation comment of the item.
+    pub doc: Option<String>,
+    /// Overrides the `strip` option for the item.
+    #[allow(clippy::option_option, reason = "`Some(None)` means stripping all")]
+    pub strip: Option<Option<usize>>,
+    /// Overrides the `join` option for the item.
+    pub join: Option<Join>,
+    /// Whether `doc_dyn` delegates to the only field of the variant.
+    pub delegate: bool,
+    /// Whether `doc_dyn` falls back to the documentation comment of the variant, if the delegated one is `None`.
+    pub fallback: bool,
+    /// The mirror type to get the documentation comment of the field's foreign type from.
+    pub remote: Option<Path>,
+}
+
+impl ItemAttrs {
+    /// Parses options from `#[doc_for(...)]` helper attributes on a field or variant.
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut item = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("doc_for")) {
+            let parsed = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+            for meta in parsed {
+                item.apply(meta)?;
+            }
+        }
+        Ok(item)
+    }
+
+    /// Applies a single option.
+    fn apply(&mut self, meta: Meta) -> Result<()> {
+        let name = meta
+            .path()
+            .get_ident()
+            .ok_or_else(|| Error::new(meta.span(), "Expected an identifier"))?
+            .clone();
+        let value = match meta {
+            Meta::Path(_) if name == "skip" => {
+                self.skip = true;
+                return Ok(());
+            }
+            Meta::Path(_) if name == "delegate" => {
+                self.delegate = true;
+                return Ok(());
+            }
+            Meta::Path(_) if name == "fallback" => {
+                self.fallback = true;
+                return Ok(());
+            }
+            Meta::NameValue(mnv) => mnv.value,
+            _ => {
+                return Err(Error::new(meta.span(), format!("Expected `{name} = ...`")));
+            }
+        };
+        match name.to_string().as_str() {
+            "skip" => {
+                self.skip = parse_bool(value)?;
+            }
+            "doc" => {
+                self.doc = Some(parse_string(&value)?);
+            }
+            "strip" => {
+                self.strip = Some(parse_option_usize(value)?);
+            }
+            "join" => {
+                self.join = Some(parse_join(&value)?);
+            }
+            "remote" => {
+                self.remote = Some(parse_path(&value)?);
+            }
+            _ => {
+                return Err(Error::new(
+                    name.span(),
+                    format!("Unknown attribute: `{name}`"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The style to extract the documentation comment of the item with, given the default style.
+    pub fn style(&self, default: DocStyle) -> DocStyle {
+        DocStyle {
+            strip: self.strip.unwrap_or(default.strip),
+            join: self.join.unwrap_or(default.join),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use syn::parse_quote;
 
     #[test]
     fn test_parse_option_usize() {
@@ -154,10 +733,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_template() {
+        assert_eq!(
+            parse_template(&parse_quote!("serde(rename = {doc|name:kebab})")).unwrap(),
+            Template::parse("serde(rename = {doc|name:kebab})").unwrap(),
+            "Expected template with fallback and modifier"
+        );
+        assert!(
+            parse_template(&parse_quote!("error({doc:unknown})")).is_err(),
+            "Expected error for unknown modifier"
+        );
+        assert!(
+            parse_template(&parse_quote!(5)).is_err(),
+            "Expected error for `5`"
+        );
+    }
+
     #[test]
     fn test_parse_attrs() {
         let parsed: MacroAttrs = parse_quote!(
             strip = all,
+            join = paragraph,
             doc_for = false,
             doc_dyn = true,
             gen_attr = "error({doc})",
@@ -167,11 +764,253 @@ mod tests {
             parsed,
             MacroAttrs {
                 strip: None,
+                join: Join::Paragraph,
                 doc_for: false,
                 doc_dyn: true,
-                gen_attrs: vec!["error({doc})".to_string(), "serde(rename = {doc})".to_string()],
+                from_doc: false,
+                gen_attrs: vec![
+                    GenAttr::new(Template::parse("error({doc})").unwrap()),
+                    GenAttr::new(Template::parse("serde(rename = {doc})").unwrap())
+                ],
+                presets: Vec::new(),
+                on_conflict: OnConflict::Error,
+                ..MacroAttrs::default()
             },
-            "Expected `strip = all, doc_for = false, doc_dyn = true, gen_attr = \"error({{doc}})\", gen_attr = \"serde(rename = {{doc}})\"`"
+            "Expected `strip = all, join = paragraph, doc_for = false, doc_dyn = true, gen_attr = \"error({{doc}})\", gen_attr = \"serde(rename = {{doc}})\"`"
+        );
+    }
+
+    #[test]
+    fn test_parse_gen_attr() {
+        let parsed: MacroAttrs = parse_quote!(
+            gen_attr(target = "container", "command(about = {doc})"),
+            gen_attr(
+                "serde(rename = {doc})",
+                target = "variant_fields",
+                target = "fields",
+                strip = all,
+                join = space,
+                on_conflict = keep,
+                include(field, 0),
+                exclude(other)
+            )
+        );
+        let mut expected = GenAttr::new(Template::parse("serde(rename = {doc})").unwrap());
+        expected.targets = vec![Target::VariantFields, Target::Fields];
+        expected.include = vec!["field".to_string(), "0".to_string()];
+        expected.exclude = vec!["other".to_string()];
+        expected.strip = Some(None);
+        expected.join = Some(Join::Space);
+        expected.on_conflict = Some(OnConflict::Keep);
+        assert_eq!(
+            parsed.gen_attrs,
+            vec![
+                GenAttr {
+                    targets: vec![Target::Container],
+                    ..GenAttr::new(Template::parse("command(about = {doc})").unwrap())
+                },
+                expected,
+            ],
+            "Expected `gen_attr` list forms"
+        );
+
+        let parsed: Result<MacroAttrs> = syn::parse_str(r#"gen_attr(target = "unknown", "x")"#);
+        assert!(parsed.is_err(), "Expected error for unknown target");
+        let parsed: Result<MacroAttrs> = syn::parse_str(r#"gen_attr(target = "fields")"#);
+        assert!(parsed.is_err(), "Expected error for missing template");
+    }
+
+    #[test]
+    fn test_parse_join() {
+        assert_eq!(
+            parse_join(&parse_quote!(space)).unwrap(),
+            Join::Space,
+            "Expected `Join::Space` for `space`"
+        );
+        assert!(
+            parse_join(&parse_quote!("space")).is_err(),
+            "Expected error for `\"space\"`"
+        );
+    }
+
+    #[test]
+    fn test_parse_on_conflict() {
+        assert_eq!(
+            parse_on_conflict(&parse_quote!(replace)).unwrap(),
+            OnConflict::Replace,
+            "Expected `OnConflict::Replace` for `replace`"
+        );
+        assert!(
+            parse_on_conflict(&parse_quote!(ignore)).is_err(),
+            "Expected error for `ignore`"
+        );
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            parse_path(&parse_quote!(my_crate::doc_for)).unwrap(),
+            parse_quote!(my_crate::doc_for),
+            "Expected path for `my_crate::doc_for`"
+        );
+        assert_eq!(
+            parse_path(&parse_quote!("::my_crate::doc_for")).unwrap(),
+            parse_quote!(::my_crate::doc_for),
+            "Expected path for `\"::my_crate::doc_for\"`"
+        );
+        assert!(
+            parse_path(&parse_quote!(5)).is_err(),
+            "Expected error for `5`"
+        );
+    }
+
+    #[test]
+    fn test_parse_vis() {
+        assert_eq!(
+            parse_vis(&parse_quote!("pub(crate)")).unwrap(),
+            parse_quote!(pub(crate)),
+            "Expected `pub(crate)` for `\"pub(crate)\"`"
+        );
+        assert_eq!(
+            parse_vis(&parse_quote!("")).unwrap(),
+            Visibility::Inherited,
+            "Expected inherited visibility for `\"\"`"
+        );
+        assert!(
+            parse_vis(&parse_quote!(public)).is_err(),
+            "Expected error for `public`"
+        );
+    }
+
+    #[test]
+    fn test_parse_fn_name() {
+        assert_eq!(
+            parse_fn_name(&parse_quote!(field_doc)).unwrap(),
+            Some(parse_quote!(field_doc)),
+            "Expected `Some` for `field_doc`"
+        );
+        assert_eq!(
+            parse_fn_name(&parse_quote!(false)).unwrap(),
+            None,
+            "Expected `None` for `false`"
+        );
+        assert!(
+            parse_fn_name(&parse_quote!(true)).is_err(),
+            "Expected error for `true`"
+        );
+    }
+
+    #[test]
+    fn test_parse_inherit() {
+        assert_eq!(
+            parse_inherit(&parse_quote!(field_type)).unwrap(),
+            Inherit::FieldType,
+            "Expected `Inherit::FieldType` for `field_type`"
+        );
+        assert!(
+            parse_inherit(&parse_quote!("field_type")).is_err(),
+            "Expected error for `\"field_type\"`"
+        );
+    }
+
+    #[test]
+    fn test_parse_preset() {
+        assert_eq!(
+            parse_preset(&parse_quote!("thiserror")).unwrap(),
+            Preset::Thiserror,
+            "Expected `Preset::Thiserror` for `\"thiserror\"`"
+        );
+        assert!(
+            parse_preset(&parse_quote!("unknown")).is_err(),
+            "Expected error for unknown preset"
+        );
+        assert!(
+            parse_preset(&parse_quote!(thiserror)).is_err(),
+            "Expected error for `thiserror`"
+        );
+    }
+
+    #[test]
+    fn test_apply_helpers() {
+        let item: syn::DeriveInput = parse_quote! {
+            #[doc_for(strip = all)]
+            #[doc_for(join = paragraph, transparent, serde_names)]
+            struct MyStruct;
+        };
+        let mut attrs = MacroAttrs::default();
+        attrs.apply_helpers(&item.attrs, true).unwrap();
+        assert!(attrs.transparent, "Expected `transparent`");
+        assert!(attrs.serde_names, "Expected `serde_names`");
+        assert_eq!(
+            attrs.style(),
+            DocStyle {
+                strip: None,
+                join: Join::Paragraph,
+            },
+            "Expected `strip = all, join = paragraph`"
+        );
+
+        let item: syn::DeriveInput = parse_quote! {
+            #[doc_for(gen_attr = "error({doc})")]
+            struct MyStruct;
+        };
+        let mut attrs = MacroAttrs::default();
+        assert!(
+            attrs.apply_helpers(&item.attrs, true).is_err(),
+            "Expected error for `gen_attr` in derive mode"
+        );
+        assert!(
+            attrs.apply_helpers(&item.attrs, false).is_ok(),
+            "Expected `gen_attr` to be accepted in attribute mode"
+        );
+    }
+
+    #[test]
+    fn test_item_attrs() {
+        let variant: syn::Variant = parse_quote! {
+            #[doc_for(skip)]
+            #[doc_for(doc = "User-facing", strip = all, join = space)]
+            Variant
+        };
+        assert_eq!(
+            ItemAttrs::from_attrs(&variant.attrs).unwrap(),
+            ItemAttrs {
+                skip: true,
+                doc: Some("User-facing".to_string()),
+                strip: Some(None),
+                join: Some(Join::Space),
+                ..ItemAttrs::default()
+            },
+            "Expected `skip, doc = \"User-facing\", strip = all, join = space`"
+        );
+
+        let variant: syn::Variant = parse_quote! {
+            #[doc_for(delegate, fallback)]
+            Variant(Inner)
+        };
+        let item = ItemAttrs::from_attrs(&variant.attrs).unwrap();
+        assert!(
+            item.delegate && item.fallback,
+            "Expected `delegate, fallback`"
+        );
+
+        let field: syn::Field = parse_quote! {
+            #[doc_for(remote = DurationDef)]
+            timeout: Duration
+        };
+        assert_eq!(
+            ItemAttrs::from_attrs(&field.attrs).unwrap().remote,
+            Some(parse_quote!(DurationDef)),
+            "Expected `remote = DurationDef`"
+        );
+
+        let variant: syn::Variant = parse_quote! {
+            #[doc_for(gen_attr = "error({doc})")]
+            Variant
+        };
+        assert!(
+            ItemAttrs::from_attrs(&variant.attrs).is_err(),
+            "Expected error for `gen_attr` on a variant"
         );
     }
 }
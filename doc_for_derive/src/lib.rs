@@ -3,21 +3,39 @@
 #![warn(clippy::all, clippy::nursery, clippy::pedantic, clippy::cargo)]
 
 mod attrs;
+mod preset;
+mod serde_attrs;
+mod template;
 
-use attrs::MacroAttrs;
+use attrs::{DocStyle, GenAttr, Inherit, ItemAttrs, Join, MacroAttrs, OnConflict, Target};
 use proc_macro::TokenStream;
 use proc_macro2::Span;
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, ToTokens};
+use serde_attrs::{RenameRule, SerdeAttrs};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 use syn::{
-    parse_macro_input, Attribute, Data, DeriveInput, Error, Expr, Fields, Ident, Lit, LitByteStr,
-    LitInt, LitStr, Meta, Result,
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Expr, Field, Fields, Ident, Index, Lit,
+    LitByteStr, LitInt, LitStr, Member, Meta, Path, Result, Token, Type, Variant, Visibility,
 };
+use template::{to_pascal_case, Context};
 
 // Helper functions
 
 /// Get the documentation comment from the attributes.
-fn get_doc(attrs: &[Attribute], strip: Option<usize>) -> Option<String> {
-    let doc_lines = attrs
+fn get_doc(attrs: &[Attribute], style: DocStyle) -> Option<String> {
+    /// Join lines with a single space, trimming all but the first line.
+    fn reflow(lines: &[&String]) -> String {
+        let mut iter = lines.iter();
+        let first = iter.next().map_or("", |line| line.trim_end()).to_string();
+        iter.fold(first, |mut acc, line| {
+            acc.push(' ');
+            acc.push_str(line.trim());
+            acc
+        })
+    }
+
+    let doc_lines: Vec<String> = attrs
         .iter()
         .filter(|attr| attr.path().is_ident("doc"))
         .filter_map(|attr| {
@@ -33,20 +51,138 @@ fn get_doc(attrs: &[Attribute], strip: Option<usize>) -> Option<String> {
             // Strip leading whitespaces
             let mut line = lit_str.value();
             let whitespaces = line.find(|c: char| !c.is_whitespace()).unwrap_or(0);
-            let count = strip.map_or(whitespaces, |n| whitespaces.min(n));
+            let count = style.strip.map_or(whitespaces, |n| whitespaces.min(n));
             line.drain(..count);
             Some(line)
-        });
-    doc_lines.reduce(|mut acc, line| {
-        acc.push('\n');
-        acc.push_str(&line);
-        acc
-    })
+        })
+        .collect();
+    if doc_lines.is_empty() {
+        return None;
+    }
+
+    let doc = match style.join {
+        Join::Newline => doc_lines.join("\n"),
+        Join::Space => {
+            let non_empty: Vec<_> = doc_lines.iter().filter(|l| !l.trim().is_empty()).collect();
+            reflow(&non_empty)
+        }
+        Join::Paragraph => doc_lines
+            .split(|l| l.trim().is_empty())
+            .filter(|paragraph| !paragraph.is_empty())
+            .map(|paragraph| reflow(&paragraph.iter().collect::<Vec<_>>()))
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    };
+    Some(doc)
+}
+
+/// Get the documentation comment for a field or variant, respecting its `#[doc_for(...)]` helper attributes.
+fn get_item_doc(attrs: &[Attribute], style: DocStyle) -> Result<Option<String>> {
+    let item = ItemAttrs::from_attrs(attrs)?;
+    if item.skip {
+        return Ok(None);
+    }
+    let style = item.style(style);
+    Ok(item.doc.or_else(|| get_doc(attrs, style)))
+}
+
+/// Stringify a type, removing the spaces that `to_string` inserts between tokens.
+fn stringify_type(ty: &Type) -> String {
+    let mut result = String::new();
+    let tokens = ty.to_token_stream().to_string();
+    let mut chars = tokens.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ' ' {
+            let prev = result.chars().last();
+            let next = chars.peek().copied();
+            let tight_prev = matches!(prev, Some('<' | ':' | '&' | '(' | '[' | '\''));
+            let tight_next = matches!(next, Some('<' | '>' | ':' | ',' | ')' | ']' | ';'));
+            if tight_prev || tight_next {
+                continue;
+            }
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Stringify a path, e.g. `serde` or `clap::arg`.
+fn stringify_path(path: &Path) -> String {
+    path.to_token_stream().to_string().replace(' ', "")
 }
 
-/// Generate the return value for a match arm, given the attributes of a field or variant. Used in the `generate_arms` and `generate_arms_index` functions.
-fn generate_arm_value(attrs: &[Attribute], strip: Option<usize>) -> proc_macro2::TokenStream {
-    let doc = get_doc(attrs, strip);
+/// Get the options of an attribute, e.g. `["rename", "default"]` for `#[serde(rename = "x", default)]`. Empty if the attribute isn't a list of options, e.g. `#[error("...")]`.
+fn attr_keys(attr: &Attribute) -> Vec<String> {
+    let Meta::List(list) = &attr.meta else {
+        return Vec::new();
+    };
+    list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+        .map(|metas| {
+            metas
+                .iter()
+                .map(|meta| stringify_path(meta.path()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Options that can be given multiple times in the same attribute, like `alias` of `serde`, `clap` and `doc`, or `serialize` of `strum`.
+const REPEATABLE_KEYS: &[&str] = &[
+    "alias",
+    "visible_alias",
+    "short_alias",
+    "visible_short_alias",
+    "serialize",
+];
+
+/// Get the options that a generated attribute shares with an existing one, or `None` if they don't conflict. Empty if the attributes conflict as a whole, i.e. they have the same path and either has no options.
+///
+/// Name-value attributes never conflict, since they can be repeated, e.g. `#[doc = "..."]` from `///` comments alongside a generated `#[doc(alias = "...")]`. Neither do options in [`REPEATABLE_KEYS`].
+fn conflicting_keys(existing: &Attribute, generated: &Attribute) -> Option<Vec<String>> {
+    let name_value = |attr: &Attribute| matches!(attr.meta, Meta::NameValue(_));
+    if name_value(existing)
+        || name_value(generated)
+        || stringify_path(existing.path()) != stringify_path(generated.path())
+    {
+        return None;
+    }
+    let existing_keys = attr_keys(existing);
+    let generated_keys = attr_keys(generated);
+    if existing_keys.is_empty() || generated_keys.is_empty() {
+        return Some(Vec::new());
+    }
+    let common: Vec<_> = generated_keys
+        .into_iter()
+        .filter(|key| existing_keys.contains(key) && !REPEATABLE_KEYS.contains(&key.as_str()))
+        .collect();
+    (!common.is_empty()).then_some(common)
+}
+
+/// Remove the given options from an attribute, returning whether the attribute should be kept. Removes the whole attribute if `keys` is empty or no option is left.
+fn remove_keys(attr: &mut Attribute, keys: &[String]) -> bool {
+    if keys.is_empty() {
+        return false;
+    }
+    let Meta::List(list) = &mut attr.meta else {
+        return false;
+    };
+    let Ok(metas) = list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) else {
+        return false;
+    };
+    let remaining: Punctuated<Meta, Token![,]> = metas
+        .into_iter()
+        .filter(|meta| !keys.contains(&stringify_path(meta.path())))
+        .collect();
+    list.tokens = remaining.to_token_stream();
+    !remaining.is_empty()
+}
+
+/// Generate the return value for a match arm, given the attributes of a field or variant.
+fn generate_arm_value(attrs: &[Attribute], style: DocStyle) -> proc_macro2::TokenStream {
+    let doc = match get_item_doc(attrs, style) {
+        Ok(doc) => doc,
+        Err(err) => return err.into_compile_error(),
+    };
     doc.map_or_else(
         || quote! { ::core::option::Option::None },
         |doc| {
@@ -56,16 +192,457 @@ fn generate_arm_value(attrs: &[Attribute], strip: Option<usize>) -> proc_macro2:
     )
 }
 
-/// Takes an iterator of (ident, attributes) pairs and generates a match expression that matches on field names. Used to generate the match arms for the `doc_for_field` method.
-fn generate_arms<I>(iter: I, strip: Option<usize>) -> proc_macro2::TokenStream
+/// A field or variant, with an expression for its documentation comment.
+#[derive(Clone)]
+struct MemberDoc {
+    /// The identifier of the field or variant. Tuple fields are named by their indices.
+    ident: String,
+    /// The span of the field or variant, for errors.
+    span: Span,
+    /// The name of the field or variant, which differs from `ident` with `serde_names`.
+    name: String,
+    /// The stringified type of the field, or `None` for variants.
+    ty: Option<String>,
+    /// Expression for the documentation comment.
+    doc: proc_macro2::TokenStream,
+    /// Expression for the `TreeFn` of the type of the field, or `None` for variants.
+    tree: proc_macro2::TokenStream,
+    /// Expression for the `FIELDS` of the type of the field, if it is flattened.
+    flatten: Option<proc_macro2::TokenStream>,
+    /// The fields of the variant. Empty for fields.
+    fields: Vec<Self>,
+}
+
+/// The rule for the fields of a variant, given the `#[serde(...)]` options of the enum: `rename_all` of the variant, or `rename_all_fields` of the enum.
+fn variant_fields_rule(container: &SerdeAttrs, variant: &Variant) -> RenameRule {
+    let rule = SerdeAttrs::from_attrs(&variant.attrs)
+        .unwrap_or_default()
+        .rename_all;
+    if rule == RenameRule::None {
+        container.rename_all_fields
+    } else {
+        rule
+    }
+}
+
+/// Get the fields, in declaration order, together with expressions for their documentation comments. Tuple fields are named by their indices.
+///
+/// Undocumented fields with `#[doc_for(remote = ...)]` fall back to the documentation comments of the given mirror types. If `inherit` is `true`, other undocumented fields fall back to the documentation comments of their types, if they implement `DocFor`.
+///
+/// If `serde` is the rule of the parent, fields are named as `#[serde(...)]` attributes specify, and skipped or flattened accordingly.
+fn field_docs<'a>(
+    fields: impl Iterator<Item = &'a Field>,
+    style: DocStyle,
+    crate_path: &Path,
+    inherit: bool,
+    serde: Option<RenameRule>,
+) -> Result<Vec<MemberDoc>> {
+    fields
+        .enumerate()
+        .filter_map(|(index, f)| {
+            let ident = f
+                .ident
+                .as_ref()
+                .map_or_else(|| index.to_string(), ToString::to_string);
+            let span = f.span();
+            let serde_attrs = match serde.map(|_| SerdeAttrs::from_attrs(&f.attrs)).transpose() {
+                Ok(serde_attrs) => serde_attrs,
+                Err(err) => return Some(Err(err)),
+            };
+            if serde_attrs.as_ref().is_some_and(|attrs| attrs.skip) {
+                return None;
+            }
+            let name = match (&serde_attrs, serde) {
+                (Some(attrs), Some(rule)) => attrs.name(&ident, rule, false),
+                _ => ident.clone(),
+            };
+            let field_ty = &f.ty;
+            let ty = Some(stringify_type(field_ty));
+            // Resolves to `None` via the fallback trait if the type doesn't implement `DocFor`
+            let probe = |item: &str| {
+                let item = Ident::new(item, Span::call_site());
+                quote! {{
+                    use #crate_path::__private::ProbeFallback as _;
+                    #crate_path::__private::Probe::<#field_ty>::#item
+                }}
+            };
+            let flatten = serde_attrs
+                .is_some_and(|attrs| attrs.flatten)
+                .then(|| probe("FIELDS"));
+            let item = match ItemAttrs::from_attrs(&f.attrs) {
+                Ok(item) => item,
+                Err(err) => return Some(Err(err)),
+            };
+            let undocumented = !item.skip && matches!(get_item_doc(&f.attrs, style), Ok(None));
+            let doc = match &item.remote {
+                Some(mirror) if undocumented => {
+                    quote! { <#mirror as #crate_path::DocFor>::DOC }
+                }
+                _ if undocumented && inherit => probe("DOC"),
+                _ => generate_arm_value(&f.attrs, style),
+            };
+            let tree = item.remote.map_or_else(
+                || probe("TREE"),
+                |mirror| quote! { ::core::option::Option::Some(#crate_path::__private::tree_fn::<#mirror>()) },
+            );
+            let fields = Vec::new();
+            Some(Ok(MemberDoc { ident, span, name, ty, doc, tree, flatten, fields }))
+        })
+        .collect()
+}
+
+/// Get the fields or variants of a type, in declaration order, together with expressions for their documentation comments. Tuple fields are named by their indices.
+fn member_docs(input: &DeriveInput, attrs: &MacroAttrs) -> Result<Vec<MemberDoc>> {
+    let style = attrs.style();
+    let crate_path = &attrs.crate_path;
+    let inherit = attrs.inherit == Inherit::FieldType;
+    let serde = attrs
+        .serde_names
+        .then(|| SerdeAttrs::from_attrs(&input.attrs))
+        .transpose()?;
+    let rule = serde.as_ref().map(|serde| serde.rename_all);
+    let members = match &input.data {
+        Data::Struct(data) => field_docs(data.fields.iter(), style, crate_path, inherit, rule)?,
+        Data::Union(data) => {
+            field_docs(data.fields.named.iter(), style, crate_path, inherit, rule)?
+        }
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .filter_map(|v| {
+                if let Err(err) = ItemAttrs::from_attrs(&v.attrs) {
+                    return Some(Err(err));
+                }
+                let ident = v.ident.to_string();
+                let name = match &serde {
+                    Some(container) => {
+                        let variant = match SerdeAttrs::from_attrs(&v.attrs) {
+                            Ok(variant) => variant,
+                            Err(err) => return Some(Err(err)),
+                        };
+                        if variant.skip {
+                            return None;
+                        }
+                        variant.name(&ident, container.rename_all, true)
+                    }
+                    None => ident.clone(),
+                };
+                let fields_rule = serde
+                    .as_ref()
+                    .map(|container| variant_fields_rule(container, v));
+                let fields =
+                    match field_docs(v.fields.iter(), style, crate_path, false, fields_rule) {
+                        Ok(fields) => fields,
+                        Err(err) => return Some(Err(err)),
+                    };
+                Some(Ok(MemberDoc {
+                    ident,
+                    span: v.ident.span(),
+                    name,
+                    ty: None,
+                    doc: generate_arm_value(&v.attrs, style),
+                    tree: quote! { ::core::option::Option::<#crate_path::__private::TreeFn>::None },
+                    flatten: None,
+                    fields,
+                }))
+            })
+            .collect::<Result<_>>()?,
+    };
+    Ok(members)
+}
+
+/// Generates an expression that builds the `DocTreeField`s for the given fields or variants, inlining flattened fields.
+fn generate_tree_fields(members: &[MemberDoc], crate_path: &Path) -> proc_macro2::TokenStream {
+    let statements = members.iter().map(|member| {
+        let MemberDoc {
+            name,
+            ty,
+            doc,
+            tree,
+            ..
+        } = member;
+        let tree = quote! {
+            match #tree {
+                ::core::option::Option::Some(build) => build(ancestors),
+                ::core::option::Option::None => ::core::option::Option::None,
+            }
+        };
+        if member.flatten.is_some() {
+            return quote! {
+                fields.extend(#tree.map_or_else(::std::vec::Vec::new, |tree| tree.fields));
+            };
+        }
+        let ty = ty.as_ref().map_or_else(
+            || quote! { ::core::option::Option::None },
+            |ty| quote! { ::core::option::Option::Some(#ty) },
+        );
+        let variant_fields = generate_tree_fields(&member.fields, crate_path);
+        quote! {
+            fields.push(#crate_path::DocTreeField {
+                name: #name,
+                ty: #ty,
+                doc: #doc,
+                fields: #variant_fields,
+                tree: #tree,
+            });
+        }
+    });
+    quote! {{
+        #[allow(unused_mut)]
+        let mut fields = ::std::vec::Vec::new();
+        #(#statements)*
+        fields
+    }}
+}
+
+/// Generates the `__doc_tree_fields` method of `DocFor`, which builds the fields or variants in the documentation tree, recursing into the types of the fields.
+fn generate_doc_tree_fields(members: &[MemberDoc], crate_path: &Path) -> proc_macro2::TokenStream {
+    let fields = generate_tree_fields(members, crate_path);
+    quote! {
+        fn __doc_tree_fields(
+            ancestors: &mut ::std::vec::Vec<::core::any::TypeId>,
+        ) -> ::core::option::Option<::std::vec::Vec<#crate_path::DocTreeField>> {
+            let id = ::core::any::TypeId::of::<Self>();
+            if ancestors.contains(&id) {
+                return ::core::option::Option::None;
+            }
+            ancestors.push(id);
+            let fields = #fields;
+            ancestors.pop();
+            ::core::option::Option::Some(fields)
+        }
+    }
+}
+
+/// Get the variant of `Kind` that describes the type.
+fn kind_variant(data: &Data) -> Ident {
+    let kind = match data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(_) => "Struct",
+            Fields::Unnamed(_) => "TupleStruct",
+            Fields::Unit => "Unit",
+        },
+        Data::Enum(_) => "Enum",
+        Data::Union(_) => "Union",
+    };
+    Ident::new(kind, Span::call_site())
+}
+
+/// Generates a table of `FieldDoc`s. Used to generate the `FIELDS` constant and the `doc_dyn_fields` method.
+///
+/// Flattened fields are replaced by the `FIELDS` of their types, by concatenating the parts in constant context.
+fn generate_fields_table(members: &[MemberDoc], crate_path: &Path) -> proc_macro2::TokenStream {
+    let entry = |MemberDoc { name, ty, doc, .. }: &MemberDoc| {
+        let ty = ty.as_ref().map_or_else(
+            || quote! { ::core::option::Option::None },
+            |ty| quote! { ::core::option::Option::Some(#ty) },
+        );
+        quote! { #crate_path::FieldDoc { name: #name, ty: #ty, doc: #doc }, }
+    };
+    if members.iter().all(|member| member.flatten.is_none()) {
+        let entries = members.iter().map(entry);
+        return quote! { &[#(#entries)*] };
+    }
+    let parts = members
+        .chunk_by(|a, b| a.flatten.is_none() && b.flatten.is_none())
+        .map(|chunk| {
+            chunk[0].flatten.clone().unwrap_or_else(|| {
+                let entries = chunk.iter().map(entry);
+                quote! { &[#(#entries)*] }
+            })
+        });
+    quote! {{
+        const PARTS: &[&[#crate_path::FieldDoc]] = &[#(#parts),*];
+        const LEN: usize = #crate_path::__private::total_len(PARTS);
+        const TABLE: [#crate_path::FieldDoc; LEN] = #crate_path::__private::concat::<LEN>(PARTS);
+        &TABLE
+    }}
+}
+
+/// Generates an enum named `{name}Field` with one variant per field or variant, along with `ALL`, `doc` and `name`. Tuple fields are mapped to variants like `_0`.
+fn generate_field_enum(
+    name: &Ident,
+    vis: &Visibility,
+    members: &[MemberDoc],
+) -> proc_macro2::TokenStream {
+    if members.iter().any(|member| member.flatten.is_some()) {
+        return Error::new(name.span(), "`field_enum` doesn't support flattened fields")
+            .into_compile_error();
+    }
+    let enum_name = format_ident!("{name}Field");
+    let enum_doc = format!(" Fields or variants of [`{name}`].");
+    let mut variants = Vec::new();
+    for MemberDoc {
+        ident: member,
+        span,
+        ty,
+        ..
+    } in members
+    {
+        // Variants are kept as-is, while fields are converted to `PascalCase`
+        let variant = if ty.is_none() {
+            member.clone()
+        } else if member.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("_{member}")
+        } else {
+            to_pascal_case(member.strip_prefix("r#").unwrap_or(member))
+        };
+        let Ok(variant) = syn::parse_str::<Ident>(&variant) else {
+            let message =
+                format!("`{member}` maps to `{variant}` in `field_enum`, which is a keyword");
+            return Error::new(*span, message).into_compile_error();
+        };
+        variants.push(variant);
+    }
+    // Different identifiers may convert to the same variant, like `field_1` and `field1`
+    for (i, variant) in variants.iter().enumerate() {
+        if let Some(first) = variants[..i].iter().position(|other| other == variant) {
+            let message = format!(
+                "`{}` and `{}` both map to `{enum_name}::{variant}` in `field_enum`",
+                members[first].ident, members[i].ident
+            );
+            return Error::new(members[i].span, message).into_compile_error();
+        }
+    }
+    let count = variants.len();
+    let doc_arms = members
+        .iter()
+        .zip(&variants)
+        .map(|(MemberDoc { doc, .. }, variant)| quote! { Self::#variant => #doc, });
+    let name_arms = members
+        .iter()
+        .zip(&variants)
+        .map(|(MemberDoc { name: member, .. }, variant)| quote! { Self::#variant => #member, });
+    quote! {
+        #[doc = #enum_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[allow(non_camel_case_types)]
+        #vis enum #enum_name {
+            #(#variants,)*
+        }
+
+        impl #enum_name {
+            /// All fields or variants, in declaration order.
+            pub const ALL: [Self; #count] = [#(Self::#variants,)*];
+
+            /// The documentation comment for the field or variant.
+            pub const fn doc(self) -> ::core::option::Option<&'static str> {
+                match self {
+                    #(#doc_arms)*
+                }
+            }
+
+            /// The name of the field or variant. Tuple fields are named by their indices.
+            pub const fn name(self) -> &'static str {
+                match self {
+                    #(#name_arms)*
+                }
+            }
+        }
+    }
+}
+
+/// Get the primitive integer type from the `#[repr(...)]` attributes, if any.
+fn repr_type(attrs: &[Attribute]) -> Option<Ident> {
+    const PRIMITIVES: [&str; 12] = [
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .find(|ident| PRIMITIVES.contains(&ident.to_string().as_str()))
+}
+
+/// Generates methods for getting the documentation comment of a variant by its discriminant, named `fn_name` and `try_{fn_name}`. Returns `None` if the type isn't an enum with a primitive representation and unit variants.
+fn generate_discriminant_fns(
+    input: &DeriveInput,
+    vis: &Visibility,
+    fn_name: &Ident,
+    style: DocStyle,
+) -> Option<proc_macro2::TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return None;
+    };
+    let repr = repr_type(&input.attrs)?;
+    if data
+        .variants
+        .iter()
+        .any(|v| !matches!(v.fields, Fields::Unit))
+    {
+        return None;
+    }
+    let name = &input.ident;
+    let try_fn_name = format_ident!("try_{fn_name}");
+    // Patterns can't contain casts, so we compare the value with each discriminant instead
+    let checks = data.variants.iter().map(|v| {
+        let ident = &v.ident;
+        let arm_value = generate_arm_value(&v.attrs, style);
+        quote! {
+            if discriminant == Self::#ident as #repr {
+                return ::core::option::Option::Some(#arm_value);
+            }
+        }
+    });
+    Some(quote! {
+        impl #name {
+            #vis const fn #fn_name(discriminant: #repr) -> ::core::option::Option<&'static str> {
+                match Self::#try_fn_name(discriminant) {
+                    ::core::option::Option::Some(doc) => doc,
+                    ::core::option::Option::None => ::core::panic!("The discriminant does not exist"),
+                }
+            }
+
+            #vis const fn #try_fn_name(discriminant: #repr) -> ::core::option::Option<::core::option::Option<&'static str>> {
+                #(#checks)*
+                ::core::option::Option::None
+            }
+        }
+    })
+}
+
+/// Generates implementation for `DocVariants`. Returns `None` if the type isn't a unit-only enum.
+fn generate_doc_variants_impl(
+    input: &DeriveInput,
+    crate_path: &Path,
+    style: DocStyle,
+) -> Option<proc_macro2::TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return None;
+    };
+    if data
+        .variants
+        .iter()
+        .any(|v| !matches!(v.fields, Fields::Unit))
+    {
+        return None;
+    }
+    let name = &input.ident;
+    let entries = data.variants.iter().map(|v| {
+        let ident = &v.ident;
+        let variant_name = ident.to_string();
+        let arm_value = generate_arm_value(&v.attrs, style);
+        quote! { (Self::#ident, #variant_name, #arm_value), }
+    });
+    Some(quote! {
+        impl #crate_path::DocVariants for #name {
+            const VARIANTS: &'static [(Self, &'static str, ::core::option::Option<&'static str>)] = &[#(#entries)*];
+        }
+    })
+}
+
+/// Takes an iterator of (name, documentation comment) pairs and generates a match expression that matches on field names. Used to generate the match arms for the `doc_for_field` method.
+fn generate_arms<I>(iter: I) -> proc_macro2::TokenStream
 where
-    I: Iterator<Item = (Ident, Vec<syn::Attribute>)>,
+    I: Iterator<Item = (String, proc_macro2::TokenStream)>,
 {
-    let arms = iter.map(|(ident, attrs)| {
-        let field_or_variant = ident.to_string();
+    let arms = iter.map(|(field_or_variant, arm_value)| {
         // Convert the name to a byte string literal (Rust doesn't allow matching on string literals in const functions).
         let field_or_variant = LitByteStr::new(field_or_variant.as_bytes(), Span::call_site());
-        let arm_value = generate_arm_value(&attrs, strip);
         quote! { #field_or_variant => #arm_value, }
     });
     quote! {
@@ -77,14 +654,13 @@ where
     }
 }
 
-/// Takes an iterator of attributes and generates a match expression that matches on field indices. Used to generate the match arms for the `doc_for_field` method.
-fn generate_arms_index<I>(iter: I, strip: Option<usize>) -> proc_macro2::TokenStream
+/// Takes an iterator of documentation comments and generates a match expression that matches on field indices. Used to generate the match arms for the `doc_for_field` and `doc_for_index` methods.
+fn generate_arms_index<I>(iter: I) -> proc_macro2::TokenStream
 where
-    I: Iterator<Item = Vec<syn::Attribute>>,
+    I: Iterator<Item = proc_macro2::TokenStream>,
 {
-    let arms = iter.enumerate().map(|(field_index, attrs)| {
+    let arms = iter.enumerate().map(|(field_index, arm_value)| {
         let field_index = LitInt::new(&field_index.to_string(), Span::call_site());
-        let arm_value = generate_arm_value(&attrs, strip);
         quote! { #field_index => #arm_value, }
     });
     quote! {
@@ -95,35 +671,115 @@ where
     }
 }
 
-/// Takes an iterator of (ident, attributes) pairs and generates a match expression that matches on varients. Used to generate the match arms for the `doc_dyn` method.
-fn generate_arms_enum<I>(iter: I, strip: Option<usize>) -> proc_macro2::TokenStream
+/// Match arms for the methods of `DocDyn`, generated by `generate_arms_enum`.
+struct DocDynArms {
+    /// Arms for `doc_dyn`.
+    doc: Vec<proc_macro2::TokenStream>,
+    /// Arms for `doc_dyn_variant`.
+    variant: Vec<proc_macro2::TokenStream>,
+    /// Arms for `doc_dyn_source`.
+    source: Vec<proc_macro2::TokenStream>,
+    /// Arms for `doc_dyn_fields`.
+    fields: Vec<proc_macro2::TokenStream>,
+}
+
+/// Takes an iterator of variants and generates match arms that match on them. Used to generate the `doc_dyn`, `doc_dyn_variant`, `doc_dyn_source` and `doc_dyn_fields` methods.
+///
+/// If `serde` is given, the fields of the variants are named as `#[serde(...)]` attributes specify.
+fn generate_arms_enum<'a, I>(
+    iter: I,
+    crate_path: &Path,
+    style: DocStyle,
+    serde: Option<&SerdeAttrs>,
+) -> Result<DocDynArms>
 where
-    I: Iterator<Item = (Ident, Vec<syn::Attribute>)>,
+    I: Iterator<Item = &'a Variant>,
 {
-    let arms = iter.map(|(ident, attrs)| {
-        let arm_value = generate_arm_value(&attrs, strip);
-        quote! { Self::#ident => #arm_value, }
-    });
-    quote! {
-        match self {
-            #(#arms)*
+    let mut arms = DocDynArms {
+        doc: Vec::new(),
+        variant: Vec::new(),
+        source: Vec::new(),
+        fields: Vec::new(),
+    };
+    for variant in iter {
+        let ident = &variant.ident;
+        let item = ItemAttrs::from_attrs(&variant.attrs)?;
+        let arm_value = generate_arm_value(&variant.attrs, style);
+        // Braced patterns work for unit, tuple and struct variants alike
+        arms.variant
+            .push(quote! { Self::#ident { .. } => #arm_value, });
+        let fields_rule = serde.map(|container| variant_fields_rule(container, variant));
+        let fields_table = generate_fields_table(
+            &field_docs(variant.fields.iter(), style, crate_path, false, fields_rule)?,
+            crate_path,
+        );
+        arms.fields
+            .push(quote! { Self::#ident { .. } => #fields_table, });
+        if !item.delegate {
+            arms.doc.push(quote! { Self::#ident { .. } => #arm_value, });
+            arms.source
+                .push(quote! { Self::#ident { .. } => ::core::option::Option::None, });
+            continue;
         }
+
+        let mut fields = variant.fields.iter();
+        let (Some(field), None) = (fields.next(), fields.next()) else {
+            return Err(Error::new_spanned(
+                variant,
+                "`delegate` requires a variant with exactly one field",
+            ));
+        };
+        let member = field
+            .ident
+            .clone()
+            .map_or_else(|| Member::Unnamed(Index::from(0)), Member::Named);
+        let pattern = quote! { Self::#ident { #member: inner } };
+        let inner_doc = quote! { #crate_path::DocDyn::doc_dyn(inner) };
+        let doc_value = if item.fallback {
+            quote! { #inner_doc.or(#arm_value) }
+        } else {
+            inner_doc
+        };
+        arms.doc.push(quote! { #pattern => #doc_value, });
+        arms.source
+            .push(quote! { #pattern => ::core::option::Option::Some(inner), });
     }
+    Ok(arms)
 }
 
 // Actual macro implementations
 
-/// Generate implementation for `DocFor` and `doc_for_field` for a type, given its definition.
+/// Generate implementation for `DocFor`, `doc_for_field` and `doc_for_index` for a type, given its definition. Also generates implementation for `DocVariants` for unit-only enums.
 ///
 /// # Parameters
 ///
-/// - `strip`: The number of leading whitespace characters to strip from the documentation comments. If `None`, all will be stripped; if `Some(n)`, `n` whitespace characters will be stripped, if present.
-fn gen_doc_for_impl(input: DeriveInput, strip: Option<usize>) -> TokenStream {
-    let name = input.ident;
-    let vis = input.vis;
+/// - `attrs`: The options of the macro, e.g. how to extract the documentation comments, the path to the `doc_for` crate and the name of the generated method.
+fn gen_doc_for_impl(input: &DeriveInput, attrs: &MacroAttrs) -> TokenStream {
+    if attrs.transparent {
+        return gen_transparent_doc_for_impl(input, attrs);
+    }
+    let name = &input.ident;
+    let vis = attrs.vis.as_ref().unwrap_or(&input.vis);
+    let style = attrs.style();
+    let crate_path = &attrs.crate_path;
+    let members = match member_docs(input, attrs) {
+        Ok(members) => members,
+        Err(err) => return err.into_compile_error().into(),
+    };
+    // Names of flattened fields are only known to the `DocFor` implementations of their types
+    let flattened = members.iter().any(|member| member.flatten.is_some());
+    let fields_table = generate_fields_table(&members, crate_path);
+    let field_enum = attrs
+        .field_enum
+        .then(|| generate_field_enum(name, vis, &members));
+    let discriminant_fns = attrs
+        .discriminant_fn
+        .as_ref()
+        .and_then(|fn_name| generate_discriminant_fns(input, vis, fn_name, style));
+    let doc_variants_impl = generate_doc_variants_impl(input, crate_path, style);
 
     // Get the documentation comment for the type.
-    let doc_for_type = get_doc(&input.attrs, strip);
+    let doc_for_type = get_doc(&input.attrs, style);
     let doc_for_type_ret = doc_for_type.map_or_else(
         || quote! { ::core::option::Option::None },
         |doc| {
@@ -131,80 +787,213 @@ fn gen_doc_for_impl(input: DeriveInput, strip: Option<usize>) -> TokenStream {
             quote! { ::core::option::Option::Some(#lit_doc) }
         },
     );
+    // Mirrors of foreign types are named after the foreign types.
+    let name_str = attrs
+        .remote
+        .as_ref()
+        .and_then(|remote| remote.segments.last())
+        .map_or_else(|| name.to_string(), |segment| segment.ident.to_string());
+    let kind = kind_variant(&input.data);
+    let doc_tree_fields = generate_doc_tree_fields(&members, crate_path);
     let doc_for_type_impl = quote! {
-        impl ::doc_for::DocFor for #name {
+        impl #crate_path::DocFor for #name {
+            const NAME: &'static str = #name_str;
+            const KIND: #crate_path::Kind = #crate_path::Kind::#kind;
             const DOC: ::core::option::Option<&'static str> = #doc_for_type_ret;
+            const FIELDS: &'static [#crate_path::FieldDoc] = #fields_table;
+
+            #doc_tree_fields
         }
     };
 
     // Get the documentation comments for the fields.
     let mut numeric = false;
-    let doc_for_field_body = match input.data {
-        Data::Struct(data) => match data.fields {
-            Fields::Named(fields) => generate_arms(
-                fields
-                    .named
-                    .into_iter()
-                    .map(|f| (f.ident.unwrap(), f.attrs)),
-                strip,
-            ),
-            Fields::Unnamed(fields) => {
-                numeric = true;
-                generate_arms_index(fields.unnamed.into_iter().map(|f| f.attrs), strip)
-            }
-            Fields::Unit => quote! { ::core::option::Option::None },
-        },
-        Data::Union(data) => generate_arms(
-            data.fields
-                .named
-                .into_iter()
-                .map(|f| (f.ident.unwrap(), f.attrs)),
-            strip,
-        ),
-        Data::Enum(data) => {
-            generate_arms(data.variants.into_iter().map(|v| (v.ident, v.attrs)), strip)
+    let doc_for_field_body = match &input.data {
+        _ if flattened => quote! { #crate_path::field_doc_by_name::<Self>(field_or_variant) },
+        Data::Struct(data) if matches!(data.fields, Fields::Unnamed(_)) => {
+            numeric = true;
+            generate_arms_index(members.iter().map(|member| member.doc.clone()))
+        }
+        Data::Struct(data) if matches!(data.fields, Fields::Unit) => {
+            quote! { ::core::option::Option::None }
         }
+        _ => generate_arms(
+            members
+                .iter()
+                .map(|member| (member.name.clone(), member.doc.clone())),
+        ),
     };
     let doc_for_field_input = if numeric {
         quote! { field_index: usize }
     } else {
         quote! { field_or_variant: &'static str }
     };
-    let doc_for_field_impl = quote! {
-        impl #name {
-            #vis const fn doc_for_field(#doc_for_field_input) -> ::core::option::Option<&'static str> {
-                #doc_for_field_body
+    let doc_for_field_impl = attrs.field_fn.as_ref().map(|field_fn| {
+        quote! {
+            impl #name {
+                #vis const fn #field_fn(#doc_for_field_input) -> ::core::option::Option<&'static str> {
+                    #doc_for_field_body
+                }
             }
         }
-    };
+    });
+
+    // Get the documentation comments for the fields or variants by their positions.
+    let doc_for_index_impl = attrs.index_fn.as_ref().map(|index_fn| {
+        let doc_for_index_body = if flattened {
+            quote! { #crate_path::field_doc_by_index::<Self>(field_index) }
+        } else {
+            generate_arms_index(members.iter().map(|member| member.doc.clone()))
+        };
+        quote! {
+            impl #name {
+                #vis const fn #index_fn(field_index: usize) -> ::core::option::Option<&'static str> {
+                    #doc_for_index_body
+                }
+            }
+        }
+    });
 
     let expanded = quote! {
         #doc_for_type_impl
         #doc_for_field_impl
+        #doc_for_index_impl
+        #discriminant_fns
+        #doc_variants_impl
+        #field_enum
     };
     expanded.into()
 }
 
-/// Generate implementation for `DocDyn` for an enum, given its definition.
+/// Generate implementation for `DocFor`, `doc_for_field` and `doc_for_index` for a newtype, forwarding to the type of its only field.
 ///
 /// # Parameters
 ///
-/// - `strip`: The number of leading whitespace characters to strip from the documentation comments. If `None`, all will be stripped; if `Some(n)`, `n` whitespace characters will be stripped, if present.
-fn gen_doc_dyn_impl(input: DeriveInput, strip: Option<usize>) -> TokenStream {
+/// - `attrs`: The options of the macro, e.g. how to extract the documentation comments, the path to the `doc_for` crate and the name of the generated method.
+fn gen_transparent_doc_for_impl(input: &DeriveInput, attrs: &MacroAttrs) -> TokenStream {
     let name = &input.ident;
+    let vis = attrs.vis.as_ref().unwrap_or(&input.vis);
+    let crate_path = &attrs.crate_path;
+    let inner = match &input.data {
+        Data::Struct(data) if data.fields.len() == 1 => &data.fields.iter().next().unwrap().ty, // Safe to unwrap - we checked the length
+        _ => {
+            return Error::new_spanned(
+                input,
+                "`transparent` requires a struct with exactly one field",
+            )
+            .into_compile_error()
+            .into();
+        }
+    };
 
-    let doc_for_variant_body = match input.data {
-        Data::Enum(data) => {
-            generate_arms_enum(data.variants.into_iter().map(|v| (v.ident, v.attrs)), strip)
+    // Fall back to the documentation comment of the inner type.
+    let doc_for_type_ret = get_doc(&input.attrs, attrs.style()).map_or_else(
+        || quote! { <#inner as #crate_path::DocFor>::DOC },
+        |doc| {
+            let lit_doc = LitStr::new(&doc, Span::call_site());
+            quote! { ::core::option::Option::Some(#lit_doc) }
+        },
+    );
+    let name_str = name.to_string();
+    let doc_for_type_impl = quote! {
+        impl #crate_path::DocFor for #name {
+            const NAME: &'static str = #name_str;
+            const KIND: #crate_path::Kind = <#inner as #crate_path::DocFor>::KIND;
+            const DOC: ::core::option::Option<&'static str> = #doc_for_type_ret;
+            const FIELDS: &'static [#crate_path::FieldDoc] = <#inner as #crate_path::DocFor>::FIELDS;
+
+            fn __doc_tree_fields(
+                ancestors: &mut ::std::vec::Vec<::core::any::TypeId>,
+            ) -> ::core::option::Option<::std::vec::Vec<#crate_path::DocTreeField>> {
+                <#inner as #crate_path::DocFor>::__doc_tree_fields(ancestors)
+            }
         }
-        _ => {
-            Error::new_spanned(&input, "DocDyn can only be derived for enums").into_compile_error()
+    };
+
+    // Forward lookups of fields to the inner type. Its kind is unknown here, so fields are always looked up by name, with tuple fields named by their indices.
+    let doc_for_field_impl = attrs.field_fn.as_ref().map(|field_fn| {
+        quote! {
+            impl #name {
+                #vis const fn #field_fn(field_or_variant: &'static str) -> ::core::option::Option<&'static str> {
+                    #crate_path::field_doc_by_name::<#inner>(field_or_variant)
+                }
+            }
+        }
+    });
+    let doc_for_index_impl = attrs.index_fn.as_ref().map(|index_fn| {
+        quote! {
+            impl #name {
+                #vis const fn #index_fn(field_index: usize) -> ::core::option::Option<&'static str> {
+                    #crate_path::field_doc_by_index::<#inner>(field_index)
+                }
+            }
         }
+    });
+
+    let expanded = quote! {
+        #doc_for_type_impl
+        #doc_for_field_impl
+        #doc_for_index_impl
+    };
+    expanded.into()
+}
+
+/// Generate implementation for `DocDyn` for an enum, given its definition.
+///
+/// # Parameters
+///
+/// - `attrs`: The options of the macro, e.g. how to extract the documentation comments and the path to the `doc_for` crate.
+fn gen_doc_dyn_impl(input: &DeriveInput, attrs: &MacroAttrs) -> TokenStream {
+    let name = &input.ident;
+    let style = attrs.style();
+    let crate_path = &attrs.crate_path;
+    let serde = match attrs
+        .serde_names
+        .then(|| SerdeAttrs::from_attrs(&input.attrs))
+        .transpose()
+    {
+        Ok(serde) => serde,
+        Err(err) => return err.into_compile_error().into(),
+    };
+
+    let Data::Enum(data) = &input.data else {
+        return Error::new_spanned(input, "DocDyn can only be derived for enums")
+            .into_compile_error()
+            .into();
+    };
+    let DocDynArms {
+        doc,
+        variant,
+        source,
+        fields,
+    } = match generate_arms_enum(data.variants.iter(), crate_path, style, serde.as_ref()) {
+        Ok(arms) => arms,
+        Err(err) => return err.into_compile_error().into(),
     };
     let doc_for_variant_impl = quote! {
-        impl ::doc_for::DocDyn for #name {
+        impl #crate_path::DocDyn for #name {
             fn doc_dyn(&self) -> ::core::option::Option<&'static str> {
-                #doc_for_variant_body
+                match self {
+                    #(#doc)*
+                }
+            }
+
+            fn doc_dyn_variant(&self) -> ::core::option::Option<&'static str> {
+                match self {
+                    #(#variant)*
+                }
+            }
+
+            fn doc_dyn_source(&self) -> ::core::option::Option<&dyn #crate_path::DocDyn> {
+                match self {
+                    #(#source)*
+                }
+            }
+
+            fn doc_dyn_fields(&self) -> &'static [#crate_path::FieldDoc] {
+                match self {
+                    #(#fields)*
+                }
             }
         }
     };
@@ -215,102 +1004,332 @@ fn gen_doc_dyn_impl(input: DeriveInput, strip: Option<usize>) -> TokenStream {
     expanded.into()
 }
 
-/// Generate attributes.
-fn gen_attrs(input: &mut DeriveInput, attrs: &[String], strip: Option<usize>) -> Result<()> {
-    fn update_attrs(
-        target: &mut Vec<Attribute>,
-        attr_templates: &[String],
-        doc: &str,
-    ) -> Result<()> {
-        use syn::parse::Parser;
-
-        let doc = LitStr::new(doc, Span::call_site());
-        let doc = doc.to_token_stream().to_string();
-        for template in attr_templates {
-            // Replace {doc} placeholder with documentation string literal
-            #[allow(clippy::literal_string_with_formatting_args, reason = "Intended")]
-            let filled_template = template.replace("{doc}", &doc);
-            let attr_str = format!("#[{filled_template}]");
-
-            // Parse the attribute
-            let tokens = syn::parse_str::<proc_macro2::TokenStream>(&attr_str)?;
-            let mut attrs = Attribute::parse_outer.parse2(tokens).unwrap_or_default();
-
-            // This should give us exactly one attribute if parsing succeeded
-            if attrs.len() == 1 {
-                target.push(attrs.pop().unwrap()); // Safe to unwrap - we checked the length
-            } else {
-                return Err(Error::new(
-                    Span::call_site(),
-                    format!("Expected exactly 1 attribute, but got {}", attrs.len()),
+/// Generate implementation for `FromDoc` for a unit-only enum, given its definition.
+///
+/// # Parameters
+///
+/// - `attrs`: The options of the macro, e.g. how to extract the documentation comments and the path to the `doc_for` crate.
+fn gen_from_doc_impl(input: &DeriveInput, attrs: &MacroAttrs) -> Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let crate_path = &attrs.crate_path;
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            input,
+            "FromDoc can only be derived for enums",
+        ));
+    };
+
+    let mut docs: Vec<String> = Vec::new();
+    let mut arms = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "FromDoc can only be derived for unit-only enums",
+            ));
+        }
+        // Undocumented variants can't be parsed
+        let Some(doc) = get_item_doc(&variant.attrs, attrs.style())? else {
+            continue;
+        };
+        if docs.contains(&doc) {
+            return Err(Error::new_spanned(
+                variant,
+                format!("Another variant has the same documentation comment: {doc:?}"),
+            ));
+        }
+        let ident = &variant.ident;
+        arms.push(quote! { #doc => ::core::option::Option::Some(Self::#ident), });
+        docs.push(doc);
+    }
+
+    Ok(quote! {
+        impl #crate_path::FromDoc for #name {
+            fn from_doc(doc: &str) -> ::core::option::Option<Self> {
+                match doc {
+                    #(#arms)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    })
+}
+
+/// Generate attributes that apply to an item of the given target, identified by the given names. The documentation comment in `context` is filled in according to the style of each attribute, unless overridden by `item`.
+fn update_attrs(
+    attrs: &mut Vec<Attribute>,
+    gen_attrs: &[GenAttr],
+    target: Target,
+    names: &[String],
+    item: &ItemAttrs,
+    mut context: Context,
+    style: DocStyle,
+) -> Result<()> {
+    use syn::parse::Parser;
+
+    if item.skip {
+        return Ok(());
+    }
+    let mut generated = Vec::new();
+    for gen_attr in gen_attrs {
+        if !gen_attr.applies_to(target, names) {
+            continue;
+        }
+        // Fill placeholders, skipping the template if any of them is unavailable
+        context.doc = item
+            .doc
+            .clone()
+            .or_else(|| get_doc(attrs, item.style(gen_attr.style(style))));
+        let Some(filled_template) = gen_attr.template.fill(&context) else {
+            continue;
+        };
+        let attr_str = format!("#[{filled_template}]");
+
+        // Parse the attribute
+        let tokens = syn::parse_str::<proc_macro2::TokenStream>(&attr_str)?;
+        let mut parsed = Attribute::parse_outer.parse2(tokens).unwrap_or_default();
+
+        // This should give us exactly one attribute if parsing succeeded
+        if parsed.len() != 1 {
+            return Err(Error::new(
+                Span::call_site(),
+                format!("Expected exactly 1 attribute, but got {}", parsed.len()),
+            ));
+        }
+        let new_attr = parsed.pop().unwrap(); // Safe to unwrap - we checked the length
+
+        // Generated attributes can't be resolved against each other
+        if let Some((other, _)) = generated
+            .iter()
+            .find(|(_, other)| conflicting_keys(other, &new_attr).is_some())
+        {
+            return Err(Error::new(
+                Span::call_site(),
+                format!("Generated attributes `{other}` and `{attr_str}` conflict"),
+            ));
+        }
+
+        // Resolve conflicts with existing attributes
+        let on_conflict = gen_attr.on_conflict.unwrap_or_default();
+        let conflict = attrs
+            .iter()
+            .find(|existing| conflicting_keys(existing, &new_attr).is_some());
+        match (conflict, on_conflict) {
+            (None, _) => {}
+            (Some(_), OnConflict::Keep) => continue,
+            (Some(existing), OnConflict::Error) => {
+                return Err(Error::new_spanned(
+                    existing,
+                    format!("Conflicts with generated attribute `{attr_str}`, set `on_conflict` to `keep` or `replace` to resolve"),
                 ));
             }
+            (Some(_), OnConflict::Replace) => {
+                attrs.retain_mut(|existing| {
+                    conflicting_keys(existing, &new_attr)
+                        .is_none_or(|keys| remove_keys(existing, &keys))
+                });
+            }
         }
+        generated.push((attr_str, new_attr));
+    }
+    attrs.extend(generated.into_iter().map(|(_, attr)| attr));
 
-        Ok(())
+    Ok(())
+}
+
+/// Generate attributes for fields, given the name of the parent variant if any.
+fn update_fields<'a>(
+    fields: impl Iterator<Item = &'a mut Field>,
+    gen_attrs: &[GenAttr],
+    target: Target,
+    parent: Option<&String>,
+    style: DocStyle,
+) -> Result<()> {
+    for (index, field) in fields.enumerate() {
+        let context = Context {
+            name: field.ident.as_ref().map(ToString::to_string),
+            index: Some(index),
+            ty: Some(stringify_type(&field.ty)),
+            ..Context::default()
+        };
+        // Tuple fields are identified by their indices
+        let mut names = vec![context.name.clone().unwrap_or_else(|| index.to_string())];
+        names.extend(parent.cloned());
+        let item = ItemAttrs::from_attrs(&field.attrs)?;
+        update_attrs(
+            &mut field.attrs,
+            gen_attrs,
+            target,
+            &names,
+            &item,
+            context,
+            style,
+        )?;
     }
+    Ok(())
+}
 
+/// Generate attributes.
+fn gen_attrs(input: &mut DeriveInput, gen_attrs: &[GenAttr], style: DocStyle) -> Result<()> {
+    // Attributes for the container
+    let name = input.ident.to_string();
+    let context = Context {
+        name: Some(name.clone()),
+        ..Context::default()
+    };
+    update_attrs(
+        &mut input.attrs,
+        gen_attrs,
+        Target::Container,
+        &[name],
+        &ItemAttrs::default(),
+        context,
+        style,
+    )?;
+
+    // Attributes for fields and variants
     match &mut input.data {
         Data::Struct(data) => {
             let fields = match &mut data.fields {
                 Fields::Named(fields) => &mut fields.named,
                 Fields::Unnamed(fields) => &mut fields.unnamed,
                 Fields::Unit => {
-                    return Err(Error::new_spanned(
-                        &input,
-                        "Cannot generate field attributes for unit structs",
-                    ));
+                    if gen_attrs
+                        .iter()
+                        .any(|g| g.targets.contains(&Target::Fields))
+                    {
+                        return Err(Error::new_spanned(
+                            &input,
+                            "Cannot generate field attributes for unit structs",
+                        ));
+                    }
+                    return Ok(());
                 }
             };
-
-            for field in fields {
-                let Some(doc) = get_doc(&field.attrs, strip) else {
-                    continue;
-                };
-                update_attrs(&mut field.attrs, attrs, &doc)?;
-            }
+            update_fields(fields.iter_mut(), gen_attrs, Target::Fields, None, style)?;
         }
         Data::Union(data) => {
             let fields = &mut data.fields.named;
-
-            for field in fields {
-                let Some(doc) = get_doc(&field.attrs, strip) else {
-                    continue;
-                };
-                update_attrs(&mut field.attrs, attrs, &doc)?;
-            }
+            update_fields(fields.iter_mut(), gen_attrs, Target::Fields, None, style)?;
         }
         Data::Enum(data) => {
             let variants = &mut data.variants;
 
-            for variant in variants {
-                let Some(doc) = get_doc(&variant.attrs, strip) else {
-                    continue;
+            for (index, variant) in variants.iter_mut().enumerate() {
+                let name = variant.ident.to_string();
+                let context = Context {
+                    name: Some(name.clone()),
+                    index: Some(index),
+                    ..Context::default()
                 };
-                update_attrs(&mut variant.attrs, attrs, &doc)?;
+                let item = ItemAttrs::from_attrs(&variant.attrs)?;
+                update_attrs(
+                    &mut variant.attrs,
+                    gen_attrs,
+                    Target::Variants,
+                    std::slice::from_ref(&name),
+                    &item,
+                    context,
+                    style,
+                )?;
+                update_fields(
+                    variant.fields.iter_mut(),
+                    gen_attrs,
+                    Target::VariantFields,
+                    Some(&name),
+                    style,
+                )?;
             }
         }
-    };
+    }
 
     Ok(())
 }
 
+/// Whether the item also derives a macro that reads `#[doc_for(...)]` helper attributes, i.e. `DocFor`, `DocDyn` or `FromDoc`, in which case the helpers must be kept.
+///
+/// Only the last segment of each derive path is checked, so derives imported under another name, like `use doc_for::DocFor as Doc`, are not recognized.
+fn derives_helper_readers(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .any(|path| {
+            path.segments.last().is_some_and(|segment| {
+                ["DocFor", "DocDyn", "FromDoc"]
+                    .iter()
+                    .any(|name| segment.ident == name)
+            })
+        })
+}
+
+/// Remove `#[doc_for(...)]` helper attributes from fields and variants.
+fn remove_helpers(input: &mut DeriveInput) {
+    let is_helper = |attr: &Attribute| attr.path().is_ident("doc_for");
+    let fields: Box<dyn Iterator<Item = &mut Field>> = match &mut input.data {
+        Data::Struct(data) => Box::new(data.fields.iter_mut()),
+        Data::Union(data) => Box::new(data.fields.named.iter_mut()),
+        Data::Enum(data) => Box::new(data.variants.iter_mut().flat_map(|variant| {
+            variant.attrs.retain(|attr| !is_helper(attr));
+            variant.fields.iter_mut()
+        })),
+    };
+    for field in fields {
+        field.attrs.retain(|attr| !is_helper(attr));
+    }
+}
+
 // Derive macros
 
-/// Derives the `DocFor` trait and `doc_for_field` method for a type. Does not strip leading whitespaces.
+/// Derives the `DocFor` trait and `doc_for_field` method for a type. Does not strip leading whitespaces, unless configured otherwise.
 ///
 /// Primarily intended for use with the `doc_for!` macro, but you can also use the derived constant and method directly via `MyType::DOC` and `MyType::doc_for_field("field")`.
-#[proc_macro_derive(DocFor)]
+///
+/// For unit-only enums, the `DocVariants` trait is derived as well.
+///
+/// Options that don't mutate the item, like `strip` and `join`, can be configured with `#[doc_for(...)]` on the type. See [`macro@doc_impl`] for available options.
+#[proc_macro_derive(DocFor, attributes(doc_for))]
 pub fn doc_for_derive(input: TokenStream) -> TokenStream {
     let input: DeriveInput = parse_macro_input!(input);
-    gen_doc_for_impl(input, Some(0)) // Don't strip by default
+    let mut attrs = MacroAttrs::default(); // Don't strip by default
+    if let Err(err) = attrs.apply_helpers(&input.attrs, true) {
+        return err.into_compile_error().into();
+    }
+    gen_doc_for_impl(&input, &attrs)
 }
 
-/// Derives the `DocDyn` trait for an enum type, providing `doc_dyn` method. Does not strip leading whitespaces.
-#[proc_macro_derive(DocDyn)]
+/// Derives the `DocDyn` trait for an enum type, providing `doc_dyn` method. Does not strip leading whitespaces, unless configured otherwise.
+///
+/// Options that don't mutate the item, like `strip` and `join`, can be configured with `#[doc_for(...)]` on the type. See [`macro@doc_impl`] for available options.
+#[proc_macro_derive(DocDyn, attributes(doc_for))]
 pub fn doc_dyn_derive(input: TokenStream) -> TokenStream {
     let input: DeriveInput = parse_macro_input!(input);
-    gen_doc_dyn_impl(input, Some(0)) // Don't strip by default
+    let mut attrs = MacroAttrs::default(); // Don't strip by default
+    if let Err(err) = attrs.apply_helpers(&input.attrs, true) {
+        return err.into_compile_error().into();
+    }
+    gen_doc_dyn_impl(&input, &attrs)
+}
+
+/// Derives the `FromDoc` trait for a unit-only enum, providing `from_doc` method that gets the variant from its documentation comment. Does not strip leading whitespaces, unless configured otherwise.
+///
+/// Fails to compile if two variants have the same documentation comment. Undocumented variants are never returned.
+///
+/// Options that don't mutate the item, like `strip` and `join`, can be configured with `#[doc_for(...)]` on the type. See [`macro@doc_impl`] for available options.
+#[proc_macro_derive(FromDoc, attributes(doc_for))]
+pub fn from_doc_derive(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    let mut attrs = MacroAttrs::default(); // Don't strip by default
+    if let Err(err) = attrs.apply_helpers(&input.attrs, true) {
+        return err.into_compile_error().into();
+    }
+    gen_from_doc_impl(&input, &attrs)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
 }
 
 // Attribute macro `doc_impl`
@@ -322,31 +1341,91 @@ pub fn doc_dyn_derive(input: TokenStream) -> TokenStream {
 /// # Parameters
 ///
 /// - `strip`: The number of leading whitespace characters to strip from the documentation comments. If `all`, all will be stripped; if `n`, `n` whitespace characters will be stripped, if present. Default is `0`.
+/// - `join`: How to join lines of the documentation comments. If `newline`, lines are kept as-is; if `space`, non-empty lines are joined with a space; if `paragraph`, lines are joined with a space and paragraphs with an empty line. Default is `newline`.
 /// - `doc_for`: Whether to generate implementation for `DocFor` and `doc_for_field`. Default is `true`.
 /// - `doc_dyn`: Whether to generate implementation for `DocDyn` for an enum. Default is `false`.
-/// - `gen_attr`: An attribute to generate for each field. Can be used multiple times. Example: `#[doc_impl(strip = 0, gen_attr = ("error({doc})")]`.
+/// - `from_doc`: Whether to generate implementation for `FromDoc` for a unit-only enum. Default is `false`.
+/// - `gen_attr`: An attribute to generate for each field. Can be used multiple times. Example: `#[doc_impl(strip = 0, gen_attr = ("error({doc})")]`. Supported placeholders are `{doc}`, `{summary}`, `{name}`, `{index}` and `{type}`, which can be modified like `{name:kebab}` or `{doc:fmt_escaped}` and chained like `{doc|name}`. Use the list form to specify options, like `gen_attr(target = "container", include(field), strip = all, "error({doc})")`.
+/// - `preset`: A named set of `gen_attr` for a common crate, one of `"thiserror"`, `"serde_rename"`, `"clap_help"` and `"strum_message"`. Can be used multiple times.
+/// - `crate`: Path to the `doc_for` crate, for when it is re-exported or renamed. Example: `#[doc_impl(crate = my_crate::doc_for)]`. Default is `::doc_for`.
+/// - `field_fn`: Name of the generated method for getting the documentation comment of a field or variant, for when it collides with an existing method. If `false`, the method is not generated, leaving only trait-based access via `DocFor::FIELDS` and `doc_for!`. Default is `doc_for_field`.
+/// - `index_fn`: Same as `field_fn`, but for the generated method for getting the documentation comment of a field or variant by its position in declaration order. Default is `doc_for_index`.
+/// - `vis`: Visibility of the generated method and field enum, as a string literal. Example: `#[doc_impl(vis = "pub(crate)")]`. Default is the visibility of the type.
+/// - `discriminant_fn`: Same as `field_fn`, but for the generated methods for getting the documentation comment of a variant by its discriminant, which are only generated for enums with a primitive representation like `#[repr(u16)]` and unit variants. `doc_for_discriminant` panics if no variant has the given discriminant, while `try_doc_for_discriminant` returns `None`. Default is `doc_for_discriminant`.
+/// - `inherit`: If `field_type`, undocumented fields fall back to the documentation comments of their types, if they implement `DocFor`. Default is `none`.
+/// - `remote`: The path to a foreign type that the annotated type mirrors, e.g. `std::time::Duration`. `NAME` is then the name of the foreign type. Declare mirrors with `doc_for::remote!` to skip the boilerplate.
+/// - `serde_names`: Whether to name fields and variants as `#[serde(...)]` attributes specify for deserialization, following `rename`, `rename_all` and `rename_all_fields`, leave out skipped ones, and inline the fields of flattened ones. Can also be given as `#[doc_for(serde_names)]`. Default is `false`.
+/// - `transparent`: For a struct with exactly one field, make `DOC` fall back to the documentation comment of the field's type, and forward lookups of fields to it. `KIND` is forwarded as well, and the generated `doc_for_field` always takes a `&'static str`, so fields of an inner tuple struct are looked up like `doc_for_field("0")`. Can also be given as `#[doc_for(transparent)]`. Default is `false`.
+/// - `field_enum`: Whether to generate an enum named `{Type}Field` with one variant per field or variant, providing `ALL`, `const fn doc(self)` and `const fn name(self)`. Default is `false`.
+/// - `on_conflict`: What to do when a generated attribute has the same path and option as an existing one, e.g. `#[serde(rename = "x")]`. If `keep`, the existing attribute is kept; if `replace`, the conflicting option is removed from the existing attribute; if `error`, an error is reported. Can also be set per `gen_attr` in the list form. Default is `error`.
+///
+/// These options can also be given with `#[doc_for(...)]` helper attributes on the type, which is the only way to configure the `DocFor`, `DocDyn` and `FromDoc` derive macros. In that case, `doc_for`, `doc_dyn`, `from_doc`, `gen_attr`, `preset` and `on_conflict` are not supported.
+///
+/// The helper attributes are removed by `doc_impl`, unless the type also derives `DocFor`, `DocDyn` or `FromDoc`, which read them as well. These derives are recognized by name, so refer to them by their original names (e.g. `DocDyn` or `doc_for::DocDyn`), not renamed imports. In that case, helper attributes on the type must only use options supported by the derive macros.
+///
+/// Fields and variants accept `#[doc_for(...)]` helper attributes too, with the following options:
+///
+/// - `skip`: Treat the item as undocumented, and don't generate attributes for it.
+/// - `doc`: Use the given string instead of the documentation comment.
+/// - `strip` and `join`: Override the corresponding options for the item.
+/// - `remote`: On a field whose type is foreign, the path to a mirror type declared with the `remote` option, whose documentation comment the field falls back to if undocumented.
+/// - `delegate`: On a variant with exactly one field, make `doc_dyn` return the documentation comment of the field's value, which must implement `DocDyn`. Add `fallback` to use the documentation comment of the variant if the delegated one is `None`.
 #[proc_macro_attribute]
 pub fn doc_impl(attrs: TokenStream, input: TokenStream) -> TokenStream {
-    let attrs: MacroAttrs = match syn::parse(attrs) {
+    let mut attrs: MacroAttrs = match syn::parse(attrs) {
         Ok(attrs) => attrs,
         Err(err) => return err.into_compile_error().into(),
     };
     let mut input: DeriveInput = parse_macro_input!(input);
+
+    // Apply and remove `#[doc_for(...)]` helper attributes, since there's no derive macro to register them, unless one that reads them is present
+    if let Err(err) = attrs.apply_helpers(&input.attrs, false) {
+        return err.into_compile_error().into();
+    }
+    let keep_helpers = derives_helper_readers(&input.attrs);
+    if !keep_helpers {
+        input.attrs.retain(|attr| !attr.path().is_ident("doc_for"));
+    }
     let mut generated = TokenStream::new();
 
     if attrs.doc_for {
-        let doc_for_impl = gen_doc_for_impl(input.clone(), attrs.strip);
+        let doc_for_impl = gen_doc_for_impl(&input, &attrs);
         generated.extend(doc_for_impl);
     }
     if attrs.doc_dyn {
-        let doc_dyn_impl = gen_doc_dyn_impl(input.clone(), attrs.strip);
+        let doc_dyn_impl = gen_doc_dyn_impl(&input, &attrs);
         generated.extend(doc_dyn_impl);
     }
-    if !attrs.gen_attrs.is_empty() {
-        if let Err(err) = gen_attrs(&mut input, &attrs.gen_attrs, attrs.strip) {
-            return err.into_compile_error().into();
+    if attrs.from_doc {
+        let from_doc_impl =
+            gen_from_doc_impl(&input, &attrs).unwrap_or_else(Error::into_compile_error);
+        generated.extend(TokenStream::from(from_doc_impl));
+    }
+    // Expand presets
+    let is_enum = matches!(input.data, Data::Enum(_));
+    let mut all_gen_attrs = attrs.gen_attrs.clone();
+    for preset in &attrs.presets {
+        match preset.gen_attrs(is_enum) {
+            Ok(preset_attrs) => all_gen_attrs.extend(preset_attrs),
+            Err(msg) => {
+                return Error::new(Span::call_site(), msg)
+                    .into_compile_error()
+                    .into()
+            }
         }
     }
+    for gen_attr in &mut all_gen_attrs {
+        gen_attr.on_conflict.get_or_insert(attrs.on_conflict);
+    }
+    if !all_gen_attrs.is_empty()
+        && let Err(err) = gen_attrs(&mut input, &all_gen_attrs, attrs.style())
+    {
+        return err.into_compile_error().into();
+    }
+
+    if !keep_helpers {
+        remove_helpers(&mut input);
+    }
 
     let mut result: TokenStream = input.into_token_stream().into();
     result.extend(generated);
@@ -0,0 +1,130 @@
+//! Presets for the `gen_attr` option of `doc_impl` attribute macro.
+
+use crate::attrs::{GenAttr, Join, Target};
+use crate::template::Template;
+
+/// Named sets of `gen_attr` templates for common crates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// `thiserror`: `#[error(...)]` on enum variants, or on the struct itself.
+    Thiserror,
+    /// `serde`: `#[serde(rename = ...)]` on fields and variants.
+    SerdeRename,
+    /// `clap`: `#[arg(help = ..., long_help = ...)]` on fields, and `#[command(about = ..., long_about = ...)]` on the struct itself.
+    ClapHelp,
+    /// `strum`: `#[strum(message = ...)]` on enum variants.
+    StrumMessage,
+}
+
+impl Preset {
+    /// Names of all presets.
+    pub const NAMES: [&str; 4] = ["thiserror", "serde_rename", "clap_help", "strum_message"];
+
+    /// Parses a preset from its name, returning `None` if it is unknown.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "thiserror" => Some(Self::Thiserror),
+            "serde_rename" => Some(Self::SerdeRename),
+            "clap_help" => Some(Self::ClapHelp),
+            "strum_message" => Some(Self::StrumMessage),
+            _ => None,
+        }
+    }
+
+    /// Expands the preset to attributes to generate, given whether the annotated type is an enum. Returns an error message if the preset doesn't apply to the type.
+    #[allow(clippy::literal_string_with_formatting_args, reason = "Intended")]
+    pub fn gen_attrs(self, is_enum: bool) -> Result<Vec<GenAttr>, String> {
+        let gen_attr = |target: Target, template: &str, join: Join| GenAttr {
+            targets: vec![target],
+            strip: Some(None),
+            join: Some(join),
+            ..GenAttr::new(Template::parse(template).expect("Preset templates should be valid"))
+        };
+        match (self, is_enum) {
+            (Self::Thiserror, true) => Ok(vec![gen_attr(
+                Target::Variants,
+                "error({doc:fmt_escaped})",
+                Join::Space,
+            )]),
+            (Self::Thiserror, false) => Ok(vec![gen_attr(
+                Target::Container,
+                "error({doc:fmt_escaped})",
+                Join::Space,
+            )]),
+            (Self::SerdeRename, true) => Ok(vec![
+                gen_attr(Target::Variants, "serde(rename = {doc})", Join::Space),
+                gen_attr(Target::VariantFields, "serde(rename = {doc})", Join::Space),
+            ]),
+            (Self::SerdeRename, false) => Ok(vec![gen_attr(
+                Target::Fields,
+                "serde(rename = {doc})",
+                Join::Space,
+            )]),
+            (Self::ClapHelp, false) => Ok(vec![
+                gen_attr(
+                    Target::Container,
+                    "command(about = {summary}, long_about = {doc})",
+                    Join::Paragraph,
+                ),
+                gen_attr(
+                    Target::Fields,
+                    "arg(help = {summary}, long_help = {doc})",
+                    Join::Paragraph,
+                ),
+            ]),
+            (Self::StrumMessage, true) => Ok(vec![gen_attr(
+                Target::Variants,
+                "strum(message = {doc})",
+                Join::Space,
+            )]),
+            (Self::ClapHelp, true) => Err("Preset `clap_help` only applies to structs".to_string()),
+            (Self::StrumMessage, false) => {
+                Err("Preset `strum_message` only applies to enums".to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        for name in Preset::NAMES {
+            let preset = Preset::from_name(name);
+            assert!(preset.is_some(), "Expected preset for `{name}`");
+        }
+    }
+
+    #[test]
+    fn test_gen_attrs() {
+        for name in Preset::NAMES {
+            let preset = Preset::from_name(name).unwrap();
+            let gen_attrs: Vec<_> = [preset.gen_attrs(true), preset.gen_attrs(false)]
+                .into_iter()
+                .flatten()
+                .flatten()
+                .collect();
+            assert!(
+                !gen_attrs.is_empty(),
+                "Expected attributes for preset `{name}`"
+            );
+            for gen_attr in gen_attrs {
+                assert_eq!(
+                    gen_attr.strip,
+                    Some(None),
+                    "Expected preset `{name}` to strip all whitespaces"
+                );
+            }
+        }
+        assert!(
+            Preset::ClapHelp.gen_attrs(true).is_err(),
+            "Expected error for `clap_help` on an enum"
+        );
+        assert!(
+            Preset::StrumMessage.gen_attrs(false).is_err(),
+            "Expected error for `strum_message` on a struct"
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//! Reading `#[serde(...)]` attributes for the `serde_names` option, following the rules of `serde_derive`.
+
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Attribute, Error, Expr, ExprLit, Lit, Meta, Result, Token};
+
+/// Rules of `rename_all`, which convert the names of fields and variants.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenameRule {
+    /// Keep the names as is.
+    #[default]
+    None,
+    /// `lowercase`
+    LowerCase,
+    /// `UPPERCASE`
+    UpperCase,
+    /// `PascalCase`
+    PascalCase,
+    /// `camelCase`
+    CamelCase,
+    /// `snake_case`
+    SnakeCase,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnakeCase,
+    /// `kebab-case`
+    KebabCase,
+    /// `SCREAMING-KEBAB-CASE`
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    /// Parses a rule from its name, returning `None` if it is unknown.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "lowercase" => Some(Self::LowerCase),
+            "UPPERCASE" => Some(Self::UpperCase),
+            "PascalCase" => Some(Self::PascalCase),
+            "camelCase" => Some(Self::CamelCase),
+            "snake_case" => Some(Self::SnakeCase),
+            "SCREAMING_SNAKE_CASE" => Some(Self::ScreamingSnakeCase),
+            "kebab-case" => Some(Self::KebabCase),
+            "SCREAMING-KEBAB-CASE" => Some(Self::ScreamingKebabCase),
+            _ => None,
+        }
+    }
+
+    /// Converts the name of a variant, which is assumed to be in `PascalCase`.
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            Self::None | Self::PascalCase => variant.to_string(),
+            Self::LowerCase => variant.to_ascii_lowercase(),
+            Self::UpperCase => variant.to_ascii_uppercase(),
+            Self::CamelCase => variant[..1].to_ascii_lowercase() + &variant[1..],
+            Self::SnakeCase => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            Self::ScreamingSnakeCase => Self::SnakeCase
+                .apply_to_variant(variant)
+                .to_ascii_uppercase(),
+            Self::KebabCase => Self::SnakeCase.apply_to_variant(variant).replace('_', "-"),
+            Self::ScreamingKebabCase => Self::ScreamingSnakeCase
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+
+    /// Converts the name of a field, which is assumed to be in `snake_case`.
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Self::None | Self::LowerCase | Self::SnakeCase => field.to_string(),
+            Self::UpperCase | Self::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            Self::PascalCase => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            Self::CamelCase => {
+                let pascal = Self::PascalCase.apply_to_field(field);
+                pascal[..1].to_ascii_lowercase() + &pascal[1..]
+            }
+            Self::KebabCase => field.replace('_', "-"),
+            Self::ScreamingKebabCase => Self::ScreamingSnakeCase
+                .apply_to_field(field)
+                .replace('_', "-"),
+        }
+    }
+}
+
+/// Parses the name used when deserializing from `Expr`, given either as `"name"` or as `(serialize = "...", deserialize = "name")`. Returns `None` if only the name used when serializing is given.
+fn parse_deserialize_name(meta: &Meta) -> Result<Option<String>> {
+    let parse_str = |expr: &Expr| match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit_str),
+            ..
+        }) => Ok(lit_str.value()),
+        _ => Err(Error::new(expr.span(), "Expected string literal")),
+    };
+    match meta {
+        Meta::NameValue(mnv) => parse_str(&mnv.value).map(Some),
+        Meta::List(list) => {
+            let parsed = list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+            for meta in parsed {
+                if let Meta::NameValue(mnv) = meta
+                    && mnv.path.is_ident("deserialize")
+                {
+                    return parse_str(&mnv.value).map(Some);
+                }
+            }
+            Ok(None)
+        }
+        Meta::Path(_) => Err(Error::new(meta.span(), "Expected `= \"...\"` or `(...)`")),
+    }
+}
+
+/// Parses `RenameRule` from the name used when deserializing.
+fn parse_rename_rule(meta: &Meta) -> Result<RenameRule> {
+    let Some(name) = parse_deserialize_name(meta)? else {
+        return Ok(RenameRule::None);
+    };
+    RenameRule::from_name(&name)
+        .ok_or_else(|| Error::new(meta.span(), format!("Unknown rename rule: `{name}`")))
+}
+
+/// The `#[serde(...)]` options that affect the names users write, on a container, variant or field. Only the names used when deserializing are considered.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SerdeAttrs {
+    /// `rename` of a variant or field.
+    pub rename: Option<String>,
+    /// `rename_all` of a container or variant.
+    pub rename_all: RenameRule,
+    /// `rename_all_fields` of an enum.
+    pub rename_all_fields: RenameRule,
+    /// `flatten` of a field.
+    pub flatten: bool,
+    /// `skip` or `skip_deserializing` of a variant or field.
+    pub skip: bool,
+}
+
+impl SerdeAttrs {
+    /// Parses options from `#[serde(...)]` attributes, ignoring unrelated ones.
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut serde = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            let parsed = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+            for meta in parsed {
+                let path = meta.path();
+                if path.is_ident("rename") {
+                    if let Some(name) = parse_deserialize_name(&meta)? {
+                        serde.rename = Some(name);
+                    }
+                } else if path.is_ident("rename_all") {
+                    serde.rename_all = parse_rename_rule(&meta)?;
+                } else if path.is_ident("rename_all_fields") {
+                    serde.rename_all_fields = parse_rename_rule(&meta)?;
+                } else if path.is_ident("flatten") {
+                    serde.flatten = true;
+                } else if path.is_ident("skip") || path.is_ident("skip_deserializing") {
+                    serde.skip = true;
+                }
+            }
+        }
+        Ok(serde)
+    }
+
+    /// The name of a field or variant, given its identifier and the rule of its parent. Tuple fields are kept as their indices.
+    pub fn name(&self, ident: &str, rule: RenameRule, is_variant: bool) -> String {
+        let ident = ident.strip_prefix("r#").unwrap_or(ident);
+        match &self.rename {
+            Some(rename) => rename.clone(),
+            None if ident.starts_with(|c: char| c.is_ascii_digit()) => ident.to_string(),
+            None if is_variant => rule.apply_to_variant(ident),
+            None => rule.apply_to_field(ident),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_apply_to_variant() {
+        let expected = [
+            (RenameRule::None, "VeryTasty"),
+            (RenameRule::LowerCase, "verytasty"),
+            (RenameRule::UpperCase, "VERYTASTY"),
+            (RenameRule::PascalCase, "VeryTasty"),
+            (RenameRule::CamelCase, "veryTasty"),
+            (RenameRule::SnakeCase, "very_tasty"),
+            (RenameRule::ScreamingSnakeCase, "VERY_TASTY"),
+            (RenameRule::KebabCase, "very-tasty"),
+            (RenameRule::ScreamingKebabCase, "VERY-TASTY"),
+        ];
+        for (rule, name) in expected {
+            assert_eq!(
+                rule.apply_to_variant("VeryTasty"),
+                name,
+                "Expected `{name}` for `{rule:?}`"
+            );
+        }
+    }
+
+    #[test]
+    fn test_apply_to_field() {
+        let expected = [
+            (RenameRule::None, "very_tasty"),
+            (RenameRule::LowerCase, "very_tasty"),
+            (RenameRule::UpperCase, "VERY_TASTY"),
+            (RenameRule::PascalCase, "VeryTasty"),
+            (RenameRule::CamelCase, "veryTasty"),
+            (RenameRule::SnakeCase, "very_tasty"),
+            (RenameRule::ScreamingSnakeCase, "VERY_TASTY"),
+            (RenameRule::KebabCase, "very-tasty"),
+            (RenameRule::ScreamingKebabCase, "VERY-TASTY"),
+        ];
+        for (rule, name) in expected {
+            assert_eq!(
+                rule.apply_to_field("very_tasty"),
+                name,
+                "Expected `{name}` for `{rule:?}`"
+            );
+        }
+    }
+
+    #[test]
+    fn test_serde_attrs() {
+        let field: syn::Field = parse_quote! {
+            #[serde(default, rename(serialize = "out", deserialize = "in"))]
+            #[serde(flatten)]
+            field: Inner
+        };
+        assert_eq!(
+            SerdeAttrs::from_attrs(&field.attrs).unwrap(),
+            SerdeAttrs {
+                rename: Some("in".to_string()),
+                flatten: true,
+                ..SerdeAttrs::default()
+            },
+            "Expected `rename = \"in\"` and `flatten`"
+        );
+
+        let field: syn::Field = parse_quote! {
+            #[serde(rename(serialize = "out"), skip_deserializing)]
+            r#type: u8
+        };
+        let serde = SerdeAttrs::from_attrs(&field.attrs).unwrap();
+        assert!(serde.skip, "Expected `skip_deserializing` to skip");
+        assert_eq!(
+            serde.name("r#type", RenameRule::UpperCase, false),
+            "TYPE",
+            "Expected `TYPE` for `r#type` with `UPPERCASE`"
+        );
+
+        let input: syn::DeriveInput = parse_quote! {
+            #[serde(rename_all = "kebab-case", rename_all_fields(deserialize = "camelCase"))]
+            enum MyEnum {}
+        };
+        let serde = SerdeAttrs::from_attrs(&input.attrs).unwrap();
+        assert_eq!(
+            serde.rename_all,
+            RenameRule::KebabCase,
+            "Expected `kebab-case` for `rename_all`"
+        );
+        assert_eq!(
+            serde.rename_all_fields,
+            RenameRule::CamelCase,
+            "Expected `camelCase` for `rename_all_fields`"
+        );
+
+        let input: syn::DeriveInput = parse_quote! {
+            #[serde(rename_all = "Title Case")]
+            struct MyStruct;
+        };
+        assert!(
+            SerdeAttrs::from_attrs(&input.attrs).is_err(),
+            "Expected error for unknown rule `Title Case`"
+        );
+    }
+}
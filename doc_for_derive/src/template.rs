@@ -0,0 +1,528 @@
+//! Templates for the `gen_attr` option of `doc_impl` attribute macro.
+//!
+//! A template is an attribute (without the surrounding `#[...]`) that may contain placeholders, e.g. `serde(rename = {doc|name:kebab})`. A placeholder consists of one or more alternatives separated by `|`, each being a key optionally followed by `:`-separated modifiers. The first alternative with an available value is used; if none is available, the template is skipped for that item.
+
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::LitStr;
+
+/// Keys that can be used in placeholders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    /// The documentation comment.
+    Doc,
+    /// The first line of the documentation comment.
+    Summary,
+    /// The name of the field or variant.
+    Name,
+    /// The index of the field or variant.
+    Index,
+    /// The type of the field.
+    Type,
+}
+
+impl Key {
+    /// Parses a key from its name, returning `None` if it is unknown.
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "doc" => Some(Self::Doc),
+            "summary" => Some(Self::Summary),
+            "name" => Some(Self::Name),
+            "index" => Some(Self::Index),
+            "type" => Some(Self::Type),
+            _ => None,
+        }
+    }
+}
+
+/// Modifiers that can be applied to the value of a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Modifier {
+    /// `lower`: all lowercase, e.g. `myfield`.
+    Lower,
+    /// `upper`: all uppercase, e.g. `MYFIELD`.
+    Upper,
+    /// `pascal`: e.g. `MyField`.
+    Pascal,
+    /// `camel`: e.g. `myField`.
+    Camel,
+    /// `snake`: e.g. `my_field`.
+    Snake,
+    /// `screaming_snake`: e.g. `MY_FIELD`.
+    ScreamingSnake,
+    /// `kebab`: e.g. `my-field`.
+    Kebab,
+    /// `screaming_kebab`: e.g. `MY-FIELD`.
+    ScreamingKebab,
+    /// `fmt_escaped`: escape `{` and `}` as `{{` and `}}`, so that the value can be used in format strings.
+    FmtEscaped,
+    /// `raw`: insert the value as-is, instead of as a string literal. Inside a string literal of the template, `\` and `"` are still escaped.
+    Raw,
+}
+
+impl Modifier {
+    /// Parses a modifier from its name, returning `None` if it is unknown.
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "lower" => Some(Self::Lower),
+            "upper" => Some(Self::Upper),
+            "pascal" => Some(Self::Pascal),
+            "camel" => Some(Self::Camel),
+            "snake" => Some(Self::Snake),
+            "screaming_snake" => Some(Self::ScreamingSnake),
+            "kebab" => Some(Self::Kebab),
+            "screaming_kebab" => Some(Self::ScreamingKebab),
+            "fmt_escaped" => Some(Self::FmtEscaped),
+            "raw" => Some(Self::Raw),
+            _ => None,
+        }
+    }
+
+    /// Applies the modifier to the given text.
+    fn apply(self, text: &str) -> String {
+        match self {
+            Self::Lower => text.to_lowercase(),
+            Self::Upper => text.to_uppercase(),
+            Self::Pascal => words(text).iter().map(|w| capitalize(w)).collect(),
+            Self::Camel => words(text)
+                .iter()
+                .enumerate()
+                .map(|(i, w)| {
+                    if i == 0 {
+                        w.to_lowercase()
+                    } else {
+                        capitalize(w)
+                    }
+                })
+                .collect(),
+            Self::Snake => join_words(text, "_", str::to_lowercase),
+            Self::ScreamingSnake => join_words(text, "_", str::to_uppercase),
+            Self::Kebab => join_words(text, "-", str::to_lowercase),
+            Self::ScreamingKebab => join_words(text, "-", str::to_uppercase),
+            Self::FmtEscaped => text.replace('{', "{{").replace('}', "}}"),
+            Self::Raw => text.to_string(),
+        }
+    }
+}
+
+/// Converts an identifier to `PascalCase`.
+pub fn to_pascal_case(text: &str) -> String {
+    Modifier::Pascal.apply(text)
+}
+
+/// Splits an identifier into words, on `_`, `-`, whitespaces and case boundaries.
+fn words(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' || c.is_whitespace() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if c.is_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Uppercases the first character of a word and lowercases the rest.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect()
+    })
+}
+
+/// Splits the text into words, maps each of them and joins them with the separator.
+fn join_words(text: &str, separator: &str, map: fn(&str) -> String) -> String {
+    words(text)
+        .iter()
+        .map(|w| map(w))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// An alternative in a placeholder: a key with its modifiers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Alternative {
+    key: Key,
+    modifiers: Vec<Modifier>,
+}
+
+/// A segment of a template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    /// Text to be copied verbatim.
+    Text(String),
+    /// A placeholder, with its alternatives and whether it is inside a string literal.
+    Placeholder(Vec<Alternative>, bool),
+}
+
+/// Whether the text looks like an identifier or an integer, rather than a format spec or an expression.
+fn is_identifier(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Parses the content of a placeholder (without braces).
+///
+/// Returns `Ok(None)` if the content does not start with an identifier, or contains a modifier that is not an identifier, in which case it should be copied verbatim. Unknown keys are errors, so callers that expect format arguments like `{0}`, `{field}` or `{name:?}` should copy the content verbatim on errors too.
+fn parse_placeholder(content: &str) -> Result<Option<Vec<Alternative>>, String> {
+    let mut alternatives = Vec::new();
+    for (i, alternative) in content.split('|').enumerate() {
+        let mut parts = alternative.split(':').map(str::trim);
+        let key_name = parts.next().unwrap_or_default(); // `split` yields at least one item
+        let Some(key) = Key::from_name(key_name) else {
+            if i == 0 && !is_identifier(key_name) {
+                return Ok(None);
+            }
+            return Err(format!(
+                "Unknown placeholder `{key_name}` in `{{{content}}}`, expected one of `doc`, `summary`, `name`, `index` or `type`"
+            ));
+        };
+        let mut modifiers = Vec::new();
+        for name in parts {
+            if let Some(modifier) = Modifier::from_name(name) {
+                modifiers.push(modifier);
+            } else if is_identifier(name) {
+                return Err(format!(
+                    "Unknown placeholder modifier `{name}` in `{{{content}}}`"
+                ));
+            } else {
+                // Probably a format spec, like `{name:?}`
+                return Ok(None);
+            }
+        }
+        alternatives.push(Alternative { key, modifiers });
+    }
+    Ok(Some(alternatives))
+}
+
+/// The values available for placeholders, for a specific item.
+#[derive(Debug, Default)]
+pub struct Context {
+    /// The documentation comment.
+    pub doc: Option<String>,
+    /// The name of the field or variant.
+    pub name: Option<String>,
+    /// The index of the field or variant.
+    pub index: Option<usize>,
+    /// The stringified type of the field.
+    pub ty: Option<String>,
+}
+
+impl Context {
+    /// Renders the value for the given alternative, returning `None` if it is not available.
+    fn render(&self, alternative: &Alternative) -> Option<String> {
+        let (mut text, mut literal) = match alternative.key {
+            Key::Doc => (self.doc.clone()?, true),
+            Key::Summary => (self.doc.as_deref()?.lines().next()?.to_string(), true),
+            Key::Name => (self.name.clone()?, true),
+            Key::Index => (self.index?.to_string(), false),
+            Key::Type => (self.ty.clone()?, true),
+        };
+        for modifier in &alternative.modifiers {
+            text = modifier.apply(&text);
+            if *modifier == Modifier::Raw {
+                literal = false;
+            }
+        }
+        if literal {
+            // Insert as a string literal
+            text = LitStr::new(&text, Span::call_site())
+                .to_token_stream()
+                .to_string();
+        }
+        Some(text)
+    }
+}
+
+/// A parsed `gen_attr` template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Parses a template, returning an error message if it contains an invalid placeholder.
+    ///
+    /// `{{` and `}}` are escapes: outside string literals they become literal braces, inside string literals they are kept as-is, since the target attribute is likely to take a format string. Inside string literals, only placeholders whose alternatives are all `raw` are replaced, so format arguments like `{name}` keep referring to fields.
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut in_string = false;
+        let mut rest = template;
+        while let Some(c) = rest.chars().next() {
+            let len = match c {
+                '\\' if in_string => rest.chars().take(2).map(char::len_utf8).sum(),
+                '"' => {
+                    in_string = !in_string;
+                    1
+                }
+                '{' | '}' if rest[1..].starts_with(c) => {
+                    text.push_str(if in_string { &rest[..2] } else { &rest[..1] });
+                    rest = &rest[2..];
+                    continue;
+                }
+                '{' => {
+                    let placeholder = rest
+                        .find('}')
+                        .map(|end| (&rest[1..end], end))
+                        .filter(|(content, _)| !content.contains('{'));
+                    match placeholder {
+                        Some((content, end)) => {
+                            let alternatives = if in_string {
+                                // Format arguments are copied verbatim, even if they look like invalid placeholders
+                                parse_placeholder(content).ok().flatten().filter(|alts| {
+                                    alts.iter()
+                                        .all(|alt| alt.modifiers.contains(&Modifier::Raw))
+                                })
+                            } else {
+                                parse_placeholder(content)?
+                            };
+                            if let Some(alternatives) = alternatives {
+                                if !text.is_empty() {
+                                    segments.push(Segment::Text(std::mem::take(&mut text)));
+                                }
+                                segments.push(Segment::Placeholder(alternatives, in_string));
+                                rest = &rest[end + 1..];
+                                continue;
+                            }
+                            end + 1
+                        }
+                        // Not a placeholder, copy the brace verbatim
+                        None => 1,
+                    }
+                }
+                _ => c.len_utf8(),
+            };
+            text.push_str(&rest[..len]);
+            rest = &rest[len..];
+        }
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+        Ok(Self { segments })
+    }
+
+    /// Fills the template with values from the context, returning `None` if any placeholder has no available value.
+    pub fn fill(&self, context: &Context) -> Option<String> {
+        let mut filled = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => filled.push_str(text),
+                Segment::Placeholder(alternatives, in_string) => {
+                    let value = alternatives.iter().find_map(|alt| context.render(alt))?;
+                    if *in_string {
+                        // Keep the surrounding string literal intact
+                        filled.push_str(&value.replace('\\', "\\\\").replace('"', "\\\""));
+                    } else {
+                        filled.push_str(&value);
+                    }
+                }
+            }
+        }
+        Some(filled)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::literal_string_with_formatting_args, reason = "Intended")]
+mod tests {
+    use super::*;
+
+    fn context() -> Context {
+        Context {
+            doc: Some("First line\nSecond line".to_string()),
+            name: Some("my_field".to_string()),
+            index: Some(2),
+            ty: Some("Vec<u8>".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_modifiers() {
+        let cases = [
+            (Modifier::Lower, "myField", "myfield"),
+            (Modifier::Upper, "my_field", "MY_FIELD"),
+            (Modifier::Pascal, "my_field", "MyField"),
+            (Modifier::Camel, "my_field", "myField"),
+            (Modifier::Snake, "HTTPServer", "http_server"),
+            (Modifier::ScreamingSnake, "MyVariant", "MY_VARIANT"),
+            (Modifier::Kebab, "my_field2", "my-field2"),
+            (Modifier::ScreamingKebab, "MyVariant", "MY-VARIANT"),
+        ];
+        for (modifier, input, expected) in cases {
+            assert_eq!(
+                modifier.apply(input),
+                expected,
+                "Expected `{expected}` for `{input}` with `{modifier:?}`"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fill() {
+        let context = context();
+        let cases = [
+            ("error({doc})", r#"error("First line\nSecond line")"#),
+            ("arg(help = {summary})", r#"arg(help = "First line")"#),
+            (
+                "serde(rename = {name:kebab})",
+                r#"serde(rename = "my-field")"#,
+            ),
+            ("arg(display_order = {index})", "arg(display_order = 2)"),
+            ("doc(alias = {type})", r#"doc(alias = "Vec<u8>")"#),
+            (
+                "error(\"{0}\", {name:upper})",
+                r#"error("{0}", "MY_FIELD")"#,
+            ),
+            ("doc(alias = \"{name:raw}\")", r#"doc(alias = "my_field")"#),
+            ("cfg({name:raw})", "cfg(my_field)"),
+        ];
+        for (template, expected) in cases {
+            let filled = Template::parse(template).unwrap().fill(&context);
+            assert_eq!(
+                filled.as_deref(),
+                Some(expected),
+                "Expected `{expected}` for `{template}`"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fill_escaping() {
+        let context = Context {
+            doc: Some("Expected `{}` here".to_string()),
+            ..Context::default()
+        };
+        let cases = [
+            ("error({doc})", r#"error("Expected `{}` here")"#),
+            (
+                "error({doc:fmt_escaped})",
+                r#"error("Expected `{{}}` here")"#,
+            ),
+            (
+                "error(\"{0}: {doc:fmt_escaped:raw}\")",
+                r#"error("{0}: Expected `{{}}` here")"#,
+            ),
+        ];
+        for (template, expected) in cases {
+            let filled = Template::parse(template).unwrap().fill(&context);
+            assert_eq!(
+                filled.as_deref(),
+                Some(expected),
+                "Expected `{expected}` for `{template}`"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fill_string_literals() {
+        let cases = [
+            (
+                "error(\"bad {name}, {index} {doc:kebab}\")",
+                "error(\"bad {name}, {index} {doc:kebab}\")",
+            ),
+            (
+                "error(\"{name:raw} is {doc:unknown}\")",
+                "error(\"my_field is {doc:unknown}\")",
+            ),
+            (
+                "error(\"\\\"{name|doc:raw}\\\" {name:raw}\")",
+                "error(\"\\\"{name|doc:raw}\\\" my_field\")",
+            ),
+            (
+                "error(\"{{name:raw}}\", {{name}})",
+                "error(\"{{name:raw}}\", {name})",
+            ),
+            ("doc(alias = {{{name}}})", "doc(alias = {\"my_field\"})"),
+        ];
+        for (template, expected) in cases {
+            assert_eq!(
+                Template::parse(template)
+                    .unwrap()
+                    .fill(&context())
+                    .as_deref(),
+                Some(expected),
+                "Expected `{expected}` for `{template}`"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fill_raw_in_string_literals() {
+        let context = Context {
+            doc: Some(r#"Say "hi" to C:\"#.to_string()),
+            ..Context::default()
+        };
+        let cases = [
+            ("error(\"{doc:raw}\")", r#"error("Say \"hi\" to C:\\")"#),
+            ("error({doc:raw})", r#"error(Say "hi" to C:\)"#),
+        ];
+        for (template, expected) in cases {
+            let filled = Template::parse(template).unwrap().fill(&context);
+            assert_eq!(
+                filled.as_deref(),
+                Some(expected),
+                "Expected `{expected}` for `{template}`"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fill_fallback() {
+        let context = Context {
+            name: Some("field".to_string()),
+            ..Context::default()
+        };
+        let template = Template::parse("serde(rename = {doc|name})").unwrap();
+        assert_eq!(
+            template.fill(&context).as_deref(),
+            Some(r#"serde(rename = "field")"#),
+            "Expected fallback to `name`"
+        );
+        let template = Template::parse("error({doc})").unwrap();
+        assert!(
+            template.fill(&context).is_none(),
+            "Expected `None` for undocumented item"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(
+            Template::parse("error({doc:unknown})").is_err(),
+            "Expected error for unknown modifier"
+        );
+        assert!(
+            Template::parse("error({doc|unknown})").is_err(),
+            "Expected error for unknown key in fallback"
+        );
+        let err = Template::parse("serde(rename = {dco})").unwrap_err();
+        assert!(
+            err.contains("`dco`") && err.contains("`summary`"),
+            "Expected error listing available keys for unknown key, got `{err}`"
+        );
+        let template = Template::parse("error(\"{unknown} {name:?}\")").unwrap();
+        assert_eq!(
+            template.fill(&context()).as_deref(),
+            Some("error(\"{unknown} {name:?}\")"),
+            "Expected unknown keys and format specs to be copied verbatim"
+        );
+    }
+}
@@ -2,18 +2,412 @@
 #![deny(missing_docs)]
 #![warn(clippy::all, clippy::nursery, clippy::pedantic, clippy::cargo)]
 
-pub use doc_for_derive::{doc_impl, DocDyn, DocFor};
+pub use doc_for_derive::{doc_impl, DocDyn, DocFor, FromDoc};
+
+#[cfg(feature = "serde")]
+pub mod serde_impls;
 
 /// Trait for types that allows getting the documentation comment for the type.
 pub trait DocFor {
+    /// The name of the type. Empty by default, in which case [`Documented::type_name`] and [`DocTree`] fall back to [`core::any::type_name`].
+    const NAME: &'static str = "";
+    /// The kind of the type. Default is [`Kind::Opaque`].
+    const KIND: Kind = Kind::Opaque;
     /// The documentation comment for the type.
     const DOC: Option<&'static str>;
+    /// The documentation comments for the fields or variants, in declaration order.
+    const FIELDS: &'static [FieldDoc] = &[];
+    /// The number of fields or variants.
+    const COUNT: usize = Self::FIELDS.len();
+
+    /// The fields or variants in the documentation tree, or `None` if the type is one of `ancestors`. Used by [`DocTree::of`]; not public API.
+    #[doc(hidden)]
+    fn __doc_tree_fields(ancestors: &mut Vec<core::any::TypeId>) -> Option<Vec<DocTreeField>> {
+        let _ = ancestors;
+        Some(Self::FIELDS.iter().map(|&field| field.into()).collect())
+    }
+}
+
+/// The kind of a type that implements [`DocFor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// A struct with named fields.
+    Struct,
+    /// A tuple struct.
+    TupleStruct,
+    /// A unit struct.
+    Unit,
+    /// An enum.
+    Enum,
+    /// A union.
+    Union,
+    /// A type whose structure isn't described, e.g. a hand-written implementation that doesn't set `KIND`.
+    Opaque,
+}
+
+/// The documentation comment for a field or variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldDoc {
+    /// The name of the field or variant. Tuple fields are named by their indices.
+    pub name: &'static str,
+    /// The type of the field as written in the source, e.g. `Option<u16>`, or `None` for variants.
+    pub ty: Option<&'static str>,
+    /// The documentation comment for the field or variant.
+    pub doc: Option<&'static str>,
+}
+
+/// The documentation of a type, together with its fields or variants and the types of the fields, recursively. Serializable with the `serde` feature.
+///
+/// ```rust
+/// use doc_for::{doc_impl, DocTree};
+///
+/// /// TLS settings
+/// #[doc_impl(strip = 1)]
+/// struct Tls {
+///     /// Path to the certificate
+///     cert: String,
+/// }
+///
+/// /// Server settings
+/// #[doc_impl(strip = 1)]
+/// struct Server {
+///     /// Port to listen on
+///     port: u16,
+///     /// TLS settings, if enabled
+///     tls: Option<Tls>,
+/// }
+///
+/// let tree = DocTree::of::<Server>();
+/// assert_eq!(tree.doc.unwrap(), "Server settings");
+/// assert!(tree.fields[0].tree.is_none());
+/// let tls = tree.fields[1].tree.as_ref().unwrap();
+/// assert_eq!(tls.name, "Tls");
+/// assert_eq!(tls.fields[0].doc.unwrap(), "Path to the certificate");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DocTree {
+    /// The name of the type.
+    pub name: &'static str,
+    /// The kind of the type.
+    pub kind: Kind,
+    /// The documentation comment for the type.
+    pub doc: Option<&'static str>,
+    /// The fields or variants, in declaration order.
+    pub fields: Vec<DocTreeField>,
+}
+
+/// A field or variant in a [`DocTree`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DocTreeField {
+    /// The name of the field or variant. Tuple fields are named by their indices.
+    pub name: &'static str,
+    /// The type of the field as written in the source, or `None` for variants.
+    pub ty: Option<&'static str>,
+    /// The documentation comment for the field or variant.
+    pub doc: Option<&'static str>,
+    /// The fields of the variant, in declaration order. Empty for fields.
+    pub fields: Vec<Self>,
+    /// The documentation tree of the type of the field, or `None` if it doesn't implement `DocFor` or is recursive.
+    pub tree: Option<DocTree>,
+}
+
+/// The name of a type: [`DocFor::NAME`], or [`core::any::type_name`] if it is empty.
+fn name_of<T: DocFor>() -> &'static str {
+    if T::NAME.is_empty() {
+        core::any::type_name::<T>()
+    } else {
+        T::NAME
+    }
+}
+
+impl DocTree {
+    /// Build the documentation tree of a type.
+    #[must_use]
+    pub fn of<T: DocFor>() -> Self {
+        Self {
+            name: name_of::<T>(),
+            kind: T::KIND,
+            doc: T::DOC,
+            // Never `None`, since the root type has no ancestors
+            fields: T::__doc_tree_fields(&mut Vec::new()).unwrap_or_default(),
+        }
+    }
+
+    /// Build the documentation tree of a type, or `None` if it is one of `ancestors`.
+    fn build<T: DocFor>(ancestors: &mut Vec<core::any::TypeId>) -> Option<Self> {
+        Some(Self {
+            name: name_of::<T>(),
+            kind: T::KIND,
+            doc: T::DOC,
+            fields: T::__doc_tree_fields(ancestors)?,
+        })
+    }
+}
+
+impl From<FieldDoc> for DocTreeField {
+    fn from(field: FieldDoc) -> Self {
+        Self {
+            name: field.name,
+            ty: field.ty,
+            doc: field.doc,
+            fields: Vec::new(),
+            tree: None,
+        }
+    }
+}
+
+/// Compare two strings in constant context.
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Get the documentation comment for a field or variant of a type by its name, via [`DocFor::FIELDS`]. Tuple fields are named by their indices.
+///
+/// # Panics
+///
+/// Panics if the field or variant does not exist.
+#[must_use]
+pub const fn field_doc_by_name<T: DocFor>(name: &str) -> Option<&'static str> {
+    let mut i = 0;
+    while i < T::FIELDS.len() {
+        if str_eq(T::FIELDS[i].name, name) {
+            return T::FIELDS[i].doc;
+        }
+        i += 1;
+    }
+    panic!("The field or variant does not exist")
+}
+
+/// Get the documentation comment for a field or variant of a type by its position in declaration order, via [`DocFor::FIELDS`].
+///
+/// # Panics
+///
+/// Panics if the field or variant does not exist.
+#[must_use]
+pub const fn field_doc_by_index<T: DocFor>(index: usize) -> Option<&'static str> {
+    assert!(
+        index < T::FIELDS.len(),
+        "The field or variant does not exist"
+    );
+    T::FIELDS[index].doc
+}
+
+/// Implement `DocFor` for wrapper types, forwarding to the wrapped type.
+macro_rules! forward_doc_for {
+    ($($(#[$meta:meta])* [$($generics:tt)*] $wrapper:ty;)*) => {
+        $(
+            $(#[$meta])*
+            impl<$($generics)*> DocFor for $wrapper {
+                const NAME: &'static str = T::NAME;
+                const KIND: Kind = T::KIND;
+                const DOC: Option<&'static str> = T::DOC;
+                const FIELDS: &'static [FieldDoc] = T::FIELDS;
+
+                fn __doc_tree_fields(
+                    ancestors: &mut Vec<core::any::TypeId>,
+                ) -> Option<Vec<DocTreeField>> {
+                    T::__doc_tree_fields(ancestors)
+                }
+            }
+        )*
+    };
+}
+
+forward_doc_for! {
+    /// Forwards to `T`, so that optional fields are documented like required ones.
+    [T: DocFor] Option<T>;
+    /// Forwards to `T`.
+    [T: DocFor + ?Sized] Box<T>;
+    /// Forwards to `T`.
+    [T: DocFor + ?Sized] std::sync::Arc<T>;
+    /// Forwards to `T`.
+    [T: DocFor + ?Sized] std::rc::Rc<T>;
+    /// Forwards to `T`, the type of the elements.
+    [T: DocFor] Vec<T>;
+    /// Forwards to `T`, the type of the elements.
+    [T: DocFor, const N: usize] [T; N];
+    /// Forwards to `T`.
+    [T: DocFor + ?Sized] &T;
+}
+
+/// Dyn-safe counterpart of [`DocFor`], implemented for all types that implement it. Allows collecting differently typed values, e.g. in a `Vec<Box<dyn Documented>>`.
+pub trait Documented {
+    /// The name of the type.
+    fn type_name(&self) -> &'static str;
+    /// The documentation comment for the type.
+    fn type_doc(&self) -> Option<&'static str>;
+    /// The documentation comments for the fields or variants, in declaration order.
+    fn field_docs(&self) -> &'static [FieldDoc];
+}
+
+impl<T: DocFor> Documented for T {
+    fn type_name(&self) -> &'static str {
+        name_of::<T>()
+    }
+
+    fn type_doc(&self) -> Option<&'static str> {
+        T::DOC
+    }
+
+    fn field_docs(&self) -> &'static [FieldDoc] {
+        T::FIELDS
+    }
 }
 
 /// Trait for enums that allows getting the documentation comment for the variant.
 pub trait DocDyn {
     /// The documentation comment for the variant.
     fn doc_dyn(&self) -> Option<&'static str>;
+
+    /// The documentation comment for the variant itself, without delegating to the wrapped value. Same as [`DocDyn::doc_dyn`] by default.
+    fn doc_dyn_variant(&self) -> Option<&'static str> {
+        self.doc_dyn()
+    }
+
+    /// The documentation comments for the fields of the variant, in declaration order. Empty by default.
+    fn doc_dyn_fields(&self) -> &'static [FieldDoc] {
+        &[]
+    }
+
+    /// The wrapped value that [`DocDyn::doc_dyn`] delegates to, if any.
+    fn doc_dyn_source(&self) -> Option<&dyn DocDyn> {
+        None
+    }
+
+    /// Iterate over the documentation comments for the variants at every level, from the outermost one, following [`DocDyn::doc_dyn_source`].
+    fn doc_dyn_chain(&self) -> DocDynChain<'_>
+    where
+        Self: Sized,
+    {
+        DocDynChain::new(self)
+    }
+}
+
+/// Iterator over the documentation comments for nested variants, created by [`DocDyn::doc_dyn_chain`].
+#[derive(Clone)]
+pub struct DocDynChain<'a> {
+    next: Option<&'a dyn DocDyn>,
+}
+
+impl<'a> DocDynChain<'a> {
+    /// Create an iterator starting from the given value.
+    #[must_use]
+    pub const fn new(value: &'a dyn DocDyn) -> Self {
+        Self { next: Some(value) }
+    }
+}
+
+impl Iterator for DocDynChain<'_> {
+    type Item = Option<&'static str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = current.doc_dyn_source();
+        Some(current.doc_dyn_variant())
+    }
+}
+
+/// Trait for unit-only enums that allows iterating over all variants together with their documentation comments.
+pub trait DocVariants: Sized + 'static {
+    /// All variants in declaration order, together with their names and documentation comments.
+    const VARIANTS: &'static [(Self, &'static str, Option<&'static str>)];
+}
+
+/// Trait for unit-only enums that allows getting the variant from its documentation comment.
+pub trait FromDoc: Sized {
+    /// Get the variant whose documentation comment is exactly `doc`, returning `None` if there is none.
+    fn from_doc(doc: &str) -> Option<Self>;
+}
+
+/// Implementation details of generated code. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    use super::{DocFor, DocTree, FieldDoc};
+    use core::any::TypeId;
+    use core::marker::PhantomData;
+
+    /// Builds the documentation tree of a type, or `None` if it is one of the ancestors.
+    pub type TreeFn = fn(&mut Vec<TypeId>) -> Option<DocTree>;
+
+    /// Get the [`TreeFn`] of `T`.
+    #[must_use]
+    pub const fn tree_fn<T: DocFor>() -> TreeFn {
+        DocTree::build::<T>
+    }
+
+    /// Resolves `DOC` to `T::DOC` if `T` implements `DocFor`, or to `None` via [`ProbeFallback`] otherwise.
+    pub struct Probe<T: ?Sized>(PhantomData<T>);
+
+    impl<T: DocFor> Probe<T> {
+        /// The documentation comment for `T`.
+        pub const DOC: Option<&'static str> = T::DOC;
+        /// Builds the documentation tree of `T`.
+        pub const TREE: Option<TreeFn> = Some(tree_fn::<T>());
+        /// The documentation comments for the fields or variants of `T`.
+        pub const FIELDS: &'static [FieldDoc] = T::FIELDS;
+    }
+
+    /// Fallback for [`Probe`], used when `T` doesn't implement `DocFor`, since inherent associated constants take precedence.
+    pub trait ProbeFallback {
+        /// No documentation comment.
+        const DOC: Option<&'static str> = None;
+        /// No documentation tree.
+        const TREE: Option<TreeFn> = None;
+        /// No fields or variants.
+        const FIELDS: &'static [FieldDoc] = &[];
+    }
+
+    impl<T: ?Sized> ProbeFallback for Probe<T> {}
+
+    /// The total length of the given tables.
+    #[must_use]
+    pub const fn total_len(parts: &[&[FieldDoc]]) -> usize {
+        let mut len = 0;
+        let mut i = 0;
+        while i < parts.len() {
+            len += parts[i].len();
+            i += 1;
+        }
+        len
+    }
+
+    /// Concatenate the given tables, whose total length must be `N`. Used to inline flattened fields.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total length is not `N`.
+    #[must_use]
+    pub const fn concat<const N: usize>(parts: &[&[FieldDoc]]) -> [FieldDoc; N] {
+        const EMPTY: FieldDoc = FieldDoc {
+            name: "",
+            ty: None,
+            doc: None,
+        };
+        assert!(total_len(parts) == N, "The total length is not `N`");
+        let mut table = [EMPTY; N];
+        let (mut i, mut n) = (0, 0);
+        while i < parts.len() {
+            let mut j = 0;
+            while j < parts[i].len() {
+                table[n] = parts[i][j];
+                n += 1;
+                j += 1;
+            }
+            i += 1;
+        }
+        table
+    }
 }
 
 /// Force compile-time evaluation. Used internally.
@@ -46,7 +440,7 @@ macro_rules! force_const {
 /// assert!(doc_for!(MyStruct, not_documented).is_none());
 /// ```
 ///
-/// Also works with enums and unions.
+/// Also works with enums and unions. Fields and variants can also be looked up by their positions in declaration order, e.g. `doc_for!(MyStruct, 0)`.
 ///
 /// # Panics
 ///
@@ -61,12 +455,57 @@ macro_rules! doc_for {
         // Field
         $crate::force_const!(
             Option<&'static str>,
-            <$t>::doc_for_field(stringify!($field))
+            $crate::field_doc_by_name::<$t>(stringify!($field))
         )
     };
     ($t:ty, $index:expr) => {
-        // Tuple field
-        $crate::force_const!(Option<&'static str>, <$t>::doc_for_field($index))
+        // Field or variant by index
+        $crate::force_const!(
+            Option<&'static str>,
+            $crate::field_doc_by_index::<$t>($index)
+        )
+    };
+}
+
+/// Declare the documentation of a foreign type with a mirror definition, like `#[serde(remote = "...")]`, since the orphan rule prevents deriving `DocFor` on types from other crates.
+///
+/// The mirror type derives `DocFor`, with `NAME` set to the name of the foreign type, so documentation comments are looked up with the mirror type, e.g. `doc_for!(DurationDef)`. Fields of the foreign type can be annotated with `#[doc_for(remote = DurationDef)]`, so that they fall back to the documentation comment of the mirror type if undocumented. The fields of the mirror type are not checked against the foreign type.
+///
+/// # Example
+///
+/// ```rust
+/// use doc_for::{doc_for, doc_impl, DocFor};
+/// use std::time::Duration;
+///
+/// doc_for::remote! {
+///     Duration =>
+///     /// A span of time
+///     #[doc_for(strip = 1)]
+///     struct DurationDef {
+///         /// Whole seconds
+///         secs: u64,
+///         /// Fractional part in nanoseconds
+///         nanos: u32,
+///     }
+/// }
+///
+/// #[doc_impl(strip = 1)]
+/// struct Config {
+///     #[doc_for(remote = DurationDef)]
+///     timeout: Duration,
+/// }
+///
+/// assert_eq!(DurationDef::NAME, "Duration");
+/// assert_eq!(doc_for!(DurationDef, secs).unwrap(), "Whole seconds");
+/// assert_eq!(doc_for!(Config, timeout).unwrap(), "A span of time");
+/// ```
+#[macro_export]
+macro_rules! remote {
+    ($remote:path => $item:item) => {
+        #[derive($crate::DocFor)]
+        #[doc_for(crate = $crate, remote = $remote)]
+        #[allow(dead_code)]
+        $item
     };
 }
 
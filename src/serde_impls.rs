@@ -0,0 +1,124 @@
+//! Adapters to (de)serialize unit-only enums as the documentation comments of their variants, and `Serialize` implementations for [`DocTree`]. Requires the `serde` feature.
+//!
+//! Use the module with `#[serde(with = "doc_for::serde_impls")]` on a field, or wrap the value in [`AsDoc`]:
+//!
+//! ```rust
+//! use doc_for::{DocDyn, FromDoc, serde_impls::AsDoc};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, DocDyn, FromDoc)]
+//! #[doc_for(strip = 1)]
+//! enum Level {
+//!     /// Low level
+//!     Low,
+//!     /// High level
+//!     High,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "doc_for::serde_impls")]
+//!     level: Level,
+//!     levels: Vec<AsDoc<Level>>,
+//! }
+//!
+//! let config = Config { level: Level::High, levels: vec![AsDoc(Level::Low)] };
+//! let json = serde_json::to_string(&config).unwrap();
+//! assert_eq!(json, r#"{"level":"High level","levels":["Low level"]}"#);
+//! assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+//! ```
+
+use crate::{DocDyn, DocTree, DocTreeField, FromDoc, Kind};
+use ::serde::de::{Error as _, Unexpected};
+use ::serde::ser::{Error as _, SerializeStruct};
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serializes a variant as its documentation comment, for use with `#[serde(serialize_with = "doc_for::serde_impls::serialize")]`.
+///
+/// # Errors
+///
+/// Returns an error if the variant is not documented.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: DocDyn,
+    S: Serializer,
+{
+    let doc = value
+        .doc_dyn()
+        .ok_or_else(|| S::Error::custom("The variant is not documented"))?;
+    serializer.serialize_str(doc)
+}
+
+/// Deserializes a variant from its documentation comment, for use with `#[serde(deserialize_with = "doc_for::serde_impls::deserialize")]`.
+///
+/// # Errors
+///
+/// Returns an error if no variant has the documentation comment.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromDoc,
+    D: Deserializer<'de>,
+{
+    let doc = String::deserialize(deserializer)?;
+    T::from_doc(&doc).ok_or_else(|| {
+        D::Error::invalid_value(
+            Unexpected::Str(&doc),
+            &"the documentation comment of a variant",
+        )
+    })
+}
+
+/// Wrapper that (de)serializes a variant as its documentation comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AsDoc<T>(pub T);
+
+impl<T: DocDyn> Serialize for AsDoc<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: FromDoc> Deserialize<'de> for AsDoc<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(Self)
+    }
+}
+
+impl Serialize for Kind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (index, variant) = match self {
+            Self::Struct => (0, "Struct"),
+            Self::TupleStruct => (1, "TupleStruct"),
+            Self::Unit => (2, "Unit"),
+            Self::Enum => (3, "Enum"),
+            Self::Union => (4, "Union"),
+            Self::Opaque => (5, "Opaque"),
+        };
+        serializer.serialize_unit_variant("Kind", index, variant)
+    }
+}
+
+/// Serializes the documentation tree of a type, e.g. `{"name":"Server","kind":"Struct","doc":"...","fields":[...]}` in JSON.
+impl Serialize for DocTree {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("DocTree", 4)?;
+        state.serialize_field("name", self.name)?;
+        state.serialize_field("kind", &self.kind)?;
+        state.serialize_field("doc", &self.doc)?;
+        state.serialize_field("fields", &self.fields)?;
+        state.end()
+    }
+}
+
+/// Serializes a field or variant, e.g. `{"name":"port","ty":"u16","doc":"...","fields":[],"tree":null}` in JSON.
+impl Serialize for DocTreeField {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("DocTreeField", 5)?;
+        state.serialize_field("name", self.name)?;
+        state.serialize_field("ty", &self.ty)?;
+        state.serialize_field("doc", &self.doc)?;
+        state.serialize_field("fields", &self.fields)?;
+        state.serialize_field("tree", &self.tree)?;
+        state.end()
+    }
+}
@@ -311,3 +311,1454 @@ fn attr_doc_impl_gen_attrs() {
     assert_eq!(format!("{}", MyError::Error1), "Error1 message");
     assert_eq!(format!("{}", MyError::Error2), "Error2 message");
 }
+
+#[test]
+fn attr_doc_impl_gen_attrs_placeholders() {
+    use doc_for::doc_impl;
+    use serde::Serialize;
+
+    #[doc_impl(
+        strip = 1,
+        doc_for = false,
+        gen_attr = "serde(rename = {doc|name:kebab})"
+    )]
+    #[derive(Serialize)]
+    struct MyStruct {
+        /// renamed
+        field1: i32,
+        not_documented: i32,
+    }
+
+    let json = serde_json::to_string(&MyStruct {
+        field1: 1,
+        not_documented: 2,
+    })
+    .unwrap();
+    assert_eq!(json, r#"{"renamed":1,"not-documented":2}"#);
+}
+
+#[test]
+fn attr_doc_impl_gen_attrs_escaping() {
+    use doc_for::doc_impl;
+    use thiserror::Error;
+
+    #[doc_impl(strip = 1, doc_for = false, gen_attr = "error({doc:fmt_escaped})")]
+    #[derive(Debug, Error)]
+    enum EscapedError {
+        /// Expected `{}` here
+        Braces,
+    }
+
+    #[doc_impl(strip = 1, doc_for = false, gen_attr = "error({doc})")]
+    #[derive(Debug, Error)]
+    enum InterpolatedError {
+        /// Error with code {0}
+        Code(i32),
+    }
+
+    #[doc_impl(
+        strip = 1,
+        doc_for = false,
+        gen_attr = "error(\"[{name:raw}] {doc:raw}\")"
+    )]
+    #[derive(Debug, Error)]
+    enum QuotedError {
+        /// Expected "x" here, not \
+        Quoted,
+    }
+
+    #[doc_impl(
+        strip = 1,
+        doc_for = false,
+        gen_attr = "error(\"bad {name}, {{name}}\")"
+    )]
+    #[derive(Debug, Error)]
+    enum FormatError {
+        /// Named field
+        Field { name: &'static str },
+    }
+
+    assert_eq!(format!("{}", EscapedError::Braces), "Expected `{}` here");
+    assert_eq!(
+        format!("{}", InterpolatedError::Code(42)),
+        "Error with code 42"
+    );
+    assert_eq!(
+        format!("{}", QuotedError::Quoted),
+        r#"[Quoted] Expected "x" here, not \"#
+    );
+    assert_eq!(
+        format!("{}", FormatError::Field { name: "V" }),
+        "bad V, {name}"
+    );
+}
+
+#[test]
+fn attr_doc_impl_gen_attrs_targets() {
+    use doc_for::doc_impl;
+    use serde::Serialize;
+    use thiserror::Error;
+
+    /// Something went wrong
+    #[doc_impl(
+        strip = 1,
+        doc_for = false,
+        gen_attr(target = "container", "error({doc})")
+    )]
+    #[derive(Debug, Error)]
+    struct UnitError;
+
+    #[doc_impl(
+        strip = 1,
+        doc_for = false,
+        gen_attr(target = "variant_fields", exclude(Ignored), "serde(rename = {doc})")
+    )]
+    #[derive(Serialize)]
+    enum MyEnum {
+        Renamed {
+            /// renamed_field
+            field: i32,
+        },
+        Ignored {
+            /// ignored_field
+            field: i32,
+        },
+    }
+
+    #[doc_impl(
+        strip = 1,
+        doc_for = false,
+        gen_attr(exclude(field1), "serde(rename = {doc})")
+    )]
+    #[derive(Serialize)]
+    struct MyStruct {
+        /// field1_rename
+        field1: i32,
+        /// field2_rename
+        field2: i32,
+    }
+
+    assert_eq!(format!("{}", UnitError), "Something went wrong");
+    assert_eq!(
+        serde_json::to_string(&MyEnum::Renamed { field: 1 }).unwrap(),
+        r#"{"Renamed":{"renamed_field":1}}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&MyEnum::Ignored { field: 1 }).unwrap(),
+        r#"{"Ignored":{"field":1}}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&MyStruct {
+            field1: 1,
+            field2: 2
+        })
+        .unwrap(),
+        r#"{"field1":1,"field2_rename":2}"#
+    );
+}
+
+#[test]
+fn attr_doc_impl_join() {
+    use doc_for::{doc_for, doc_impl};
+
+    /// Some documentation
+    /// that spans multiple lines
+    ///
+    /// Additional information
+    #[doc_impl(strip = all, join = space)]
+    struct SpaceJoined;
+
+    /// Some documentation
+    /// that spans multiple lines
+    ///
+    /// Additional information
+    #[doc_impl(strip = all, join = paragraph)]
+    struct ParagraphJoined;
+
+    assert_eq!(
+        doc_for!(SpaceJoined).unwrap(),
+        "Some documentation that spans multiple lines Additional information"
+    );
+    assert_eq!(
+        doc_for!(ParagraphJoined).unwrap(),
+        "Some documentation that spans multiple lines\n\nAdditional information"
+    );
+}
+
+#[test]
+fn attr_doc_impl_presets() {
+    use clap::{CommandFactory, Parser};
+    use doc_for::doc_impl;
+    use serde::Deserialize;
+    use strum::EnumMessage;
+    use thiserror::Error;
+
+    #[doc_impl(doc_for = false, preset = "thiserror")]
+    #[derive(Debug, Error)]
+    enum MyError {
+        /// Expected `{}`
+        /// on multiple lines
+        Braces,
+    }
+
+    /// Something went wrong
+    #[doc_impl(doc_for = false, preset = "thiserror")]
+    #[derive(Debug, Error)]
+    struct UnitError;
+
+    #[doc_impl(doc_for = false, preset = "serde_rename")]
+    #[derive(Deserialize)]
+    struct MyStruct {
+        /// field_rename
+        field: i32,
+    }
+
+    /// My command
+    #[doc_impl(doc_for = false, preset = "clap_help")]
+    #[derive(Parser)]
+    #[command(name = "my-command")]
+    struct MyCommand {
+        /// The input
+        /// file
+        ///
+        /// More details
+        #[arg(long)]
+        input: String,
+    }
+
+    #[doc_impl(doc_for = false, preset = "strum_message")]
+    #[derive(strum::EnumMessage)]
+    enum MyEnum {
+        /// First
+        /// variant
+        First,
+    }
+
+    assert_eq!(
+        format!("{}", MyError::Braces),
+        "Expected `{}` on multiple lines"
+    );
+    assert_eq!(format!("{}", UnitError), "Something went wrong");
+    let my_struct: MyStruct = serde_json::from_str(r#"{"field_rename": 1}"#).unwrap();
+    assert_eq!(my_struct.field, 1);
+    let command = MyCommand::command();
+    assert_eq!(command.get_about().unwrap().to_string(), "My command");
+    let input = command.get_arguments().find(|a| a.get_id() == "input");
+    let input = input.unwrap();
+    assert_eq!(input.get_help().unwrap().to_string(), "The input file");
+    assert_eq!(
+        input.get_long_help().unwrap().to_string(),
+        "The input file\n\nMore details"
+    );
+    assert_eq!(MyEnum::First.get_message(), Some("First variant"));
+}
+
+#[test]
+fn derive_helper_attrs() {
+    use doc_for::{doc_for, doc_impl, DocDyn, DocFor};
+
+    /// Some documentation
+    /// on multiple lines
+    #[derive(DocFor, DocDyn)]
+    #[doc_for(strip = all)]
+    #[doc_for(join = space)]
+    enum MyEnum {
+        /// Variant documentation
+        Variant,
+    }
+
+    /// Some documentation
+    /// on multiple lines
+    #[doc_impl(strip = all)]
+    #[doc_for(join = space)]
+    struct MyStruct;
+
+    assert_eq!(
+        doc_for!(MyEnum).unwrap(),
+        "Some documentation on multiple lines"
+    );
+    assert_eq!(doc_for!(MyEnum, Variant).unwrap(), "Variant documentation");
+    assert_eq!(MyEnum::Variant.doc_dyn().unwrap(), "Variant documentation");
+    assert_eq!(
+        doc_for!(MyStruct).unwrap(),
+        "Some documentation on multiple lines"
+    );
+}
+
+#[test]
+fn attr_doc_impl_item_attrs() {
+    use doc_for::{doc_for, doc_impl, DocDyn, DocFor};
+    use thiserror::Error;
+
+    #[doc_impl(strip = all, doc_dyn = true, gen_attr = "error({doc|name})")]
+    #[derive(Debug, Error)]
+    enum MyError {
+        /// Internal notes
+        #[doc_for(skip)]
+        #[error("Skipped error")]
+        Skipped,
+        /// Internal notes
+        #[doc_for(doc = "Overridden error")]
+        Overridden,
+        ///   Some error
+        ///   on multiple lines
+        #[doc_for(strip = 1, join = space)]
+        Joined,
+    }
+
+    #[derive(DocFor)]
+    struct MyTupleStruct(
+        /// Internal notes
+        #[doc_for(skip)]
+        i32,
+        /// Internal notes
+        #[doc_for(doc = "User-facing text")]
+        i32,
+    );
+
+    assert_eq!(doc_for!(MyError, Skipped), None);
+    assert_eq!(MyError::Skipped.doc_dyn(), None);
+    assert_eq!(MyError::Skipped.to_string(), "Skipped error");
+    assert_eq!(doc_for!(MyError, Overridden).unwrap(), "Overridden error");
+    assert_eq!(MyError::Overridden.to_string(), "Overridden error");
+    assert_eq!(
+        doc_for!(MyError, Joined).unwrap(),
+        "  Some error on multiple lines"
+    );
+    assert_eq!(
+        MyError::Joined.to_string(),
+        "  Some error on multiple lines"
+    );
+    assert_eq!(doc_for!(MyTupleStruct, 0), None);
+    assert_eq!(doc_for!(MyTupleStruct, 1).unwrap(), "User-facing text");
+}
+
+#[test]
+fn attr_doc_impl_with_derive() {
+    use doc_for::{doc_impl, DocDyn};
+    use thiserror::Error;
+
+    #[doc_impl(strip = 1, doc_for = false, gen_attr = "error({doc})")]
+    #[derive(Debug, Error, DocDyn)]
+    #[doc_for(strip = 1)]
+    enum DbError {
+        /// Connection lost
+        Disconnected,
+    }
+
+    #[doc_impl(strip = 1, doc_for = false, gen_attr = "error({doc})")]
+    #[derive(Debug, Error, doc_for::DocDyn)]
+    enum AppError {
+        /// Database error
+        #[doc_for(delegate)]
+        Db(#[source] DbError),
+    }
+
+    // Helpers are kept for the derive macro
+    assert_eq!(
+        AppError::Db(DbError::Disconnected).doc_dyn().unwrap(),
+        "Connection lost"
+    );
+    assert_eq!(
+        AppError::Db(DbError::Disconnected).to_string(),
+        "Database error"
+    );
+}
+
+#[test]
+fn attr_doc_impl_on_conflict() {
+    use doc_for::doc_impl;
+    use serde::Serialize;
+    use thiserror::Error;
+
+    #[doc_impl(
+        strip = 1,
+        doc_for = false,
+        on_conflict = replace,
+        gen_attr = "serde(rename = {doc})",
+        gen_attr(on_conflict = keep, "error({doc})")
+    )]
+    #[derive(Debug, Error, Serialize)]
+    enum MyError {
+        /// first
+        #[serde(rename = "manual", alias = "manual")]
+        #[error("Manual error")]
+        First(Option<i32>),
+        /// second
+        Second,
+    }
+
+    assert_eq!(
+        serde_json::to_string(&MyError::First(None)).unwrap(),
+        r#"{"first":null}"#
+    );
+    assert_eq!(MyError::First(None).to_string(), "Manual error");
+    assert_eq!(
+        serde_json::to_string(&MyError::Second).unwrap(),
+        r#""second""#
+    );
+    assert_eq!(MyError::Second.to_string(), "second");
+}
+
+#[test]
+fn attr_doc_impl_doc_alias() {
+    use doc_for::{doc_for, doc_impl};
+    use serde::Deserialize;
+
+    /// Some documentation
+    #[doc_impl(strip = 1, gen_attr = "doc(alias = {type})")]
+    struct MyStruct {
+        /// Field documentation
+        field: i32,
+    }
+
+    // `alias` is repeatable, so it doesn't conflict
+    #[doc_impl(strip = 1, doc_for = false, gen_attr = "serde(alias = {doc})")]
+    #[derive(Deserialize)]
+    struct Aliased {
+        /// current
+        #[serde(alias = "legacy")]
+        field: i32,
+    }
+
+    #[doc_impl(strip = 1, doc_for = false, on_conflict = keep, gen_attr = "serde(rename = {doc})")]
+    #[derive(Deserialize)]
+    struct Kept {
+        /// generated
+        #[serde(rename = "manual")]
+        field: i32,
+    }
+
+    #[doc_impl(strip = 1, doc_for = false, on_conflict = replace, gen_attr = "serde(rename = {doc})")]
+    #[derive(Deserialize)]
+    struct Replaced {
+        /// generated
+        #[serde(rename = "manual", alias = "legacy")]
+        field: i32,
+    }
+
+    assert_eq!(doc_for!(MyStruct, field).unwrap(), "Field documentation");
+    for json in [r#"{"field":1}"#, r#"{"legacy":1}"#, r#"{"current":1}"#] {
+        assert_eq!(serde_json::from_str::<Aliased>(json).unwrap().field, 1);
+    }
+    assert_eq!(
+        serde_json::from_str::<Kept>(r#"{"manual":1}"#)
+            .unwrap()
+            .field,
+        1
+    );
+    assert!(serde_json::from_str::<Kept>(r#"{"generated":1}"#).is_err());
+    assert_eq!(
+        serde_json::from_str::<Replaced>(r#"{"generated":1}"#)
+            .unwrap()
+            .field,
+        1
+    );
+    assert_eq!(
+        serde_json::from_str::<Replaced>(r#"{"legacy":1}"#)
+            .unwrap()
+            .field,
+        1
+    );
+    assert!(serde_json::from_str::<Replaced>(r#"{"manual":1}"#).is_err());
+}
+
+#[test]
+fn attr_doc_impl_crate_and_field_fn() {
+    use doc_for::{doc_impl, DocFor};
+
+    mod facade {
+        pub use doc_for as reexported;
+    }
+
+    /// Some documentation
+    #[doc_impl(crate = facade::reexported, strip = 1, doc_dyn = true, field_fn = field_doc)]
+    enum MyEnum {
+        /// Variant documentation
+        Variant,
+    }
+
+    /// Some documentation
+    #[derive(DocFor)]
+    #[doc_for(crate = "facade::reexported", field_fn = false)]
+    struct MyStruct {
+        /// Field documentation
+        field: i32,
+    }
+
+    impl MyStruct {
+        const fn doc_for_field(_: &str) -> &'static str {
+            "Existing method"
+        }
+    }
+
+    use facade::reexported::DocDyn;
+    assert_eq!(MyEnum::DOC.unwrap(), "Some documentation");
+    assert_eq!(
+        MyEnum::field_doc("Variant").unwrap(),
+        "Variant documentation"
+    );
+    assert_eq!(MyEnum::Variant.doc_dyn().unwrap(), "Variant documentation");
+    assert_eq!(MyStruct::DOC.unwrap(), " Some documentation");
+    assert_eq!(MyStruct::doc_for_field("field"), "Existing method");
+}
+
+#[test]
+fn attr_doc_impl_vis_and_fields() {
+    use doc_for::{doc_for, field_doc_by_index, field_doc_by_name, DocFor, FieldDoc};
+
+    mod inner {
+        use doc_for::doc_impl;
+
+        /// Some documentation
+        #[doc_impl(strip = 1, vis = "pub(crate)")]
+        pub struct MyStruct {
+            /// Field documentation
+            pub field: i32,
+            pub not_documented: i32,
+        }
+
+        #[doc_impl(strip = 1, field_fn = false)]
+        pub enum MyEnum {
+            /// Variant documentation
+            Variant,
+        }
+    }
+
+    use inner::{MyEnum, MyStruct};
+    assert_eq!(
+        MyStruct::doc_for_field("field").unwrap(),
+        "Field documentation"
+    );
+    assert_eq!(
+        MyStruct::FIELDS,
+        &[
+            FieldDoc {
+                name: "field",
+                ty: Some("i32"),
+                doc: Some("Field documentation"),
+            },
+            FieldDoc {
+                name: "not_documented",
+                ty: Some("i32"),
+                doc: None,
+            },
+        ]
+    );
+    assert_eq!(
+        field_doc_by_name::<MyStruct>("field").unwrap(),
+        "Field documentation"
+    );
+    assert!(field_doc_by_index::<MyStruct>(1).is_none());
+    assert_eq!(doc_for!(MyEnum, Variant).unwrap(), "Variant documentation");
+    assert_eq!(MyEnum::FIELDS.len(), 1);
+}
+
+#[test]
+fn attr_doc_impl_field_enum() {
+    use doc_for::{doc_impl, DocFor};
+
+    #[doc_impl(strip = 1, field_enum = true)]
+    struct MyStruct {
+        /// Field documentation
+        my_field: i32,
+        r#type: i32,
+    }
+
+    #[derive(DocFor)]
+    #[doc_for(strip = 1, field_enum = true)]
+    struct MyTupleStruct(
+        /// Field documentation
+        i32,
+    );
+
+    #[doc_impl(strip = 1, field_enum = true)]
+    enum MyEnum {
+        /// Variant documentation
+        Variant,
+        HTTPServer,
+    }
+
+    assert_eq!(
+        MyStructField::ALL,
+        [MyStructField::MyField, MyStructField::Type]
+    );
+    assert_eq!(MyStructField::MyField.doc().unwrap(), "Field documentation");
+    assert_eq!(MyStructField::MyField.name(), "my_field");
+    assert_eq!(MyStructField::Type.doc(), None);
+    assert_eq!(MyStructField::Type.name(), "r#type");
+    assert_eq!(MyTupleStructField::_0.doc().unwrap(), "Field documentation");
+    assert_eq!(MyTupleStructField::_0.name(), "0");
+    assert_eq!(MyEnumField::ALL.len(), 2);
+    assert_eq!(MyEnumField::Variant.doc().unwrap(), "Variant documentation");
+    // Variants are kept as-is
+    assert_eq!(MyEnumField::HTTPServer.name(), "HTTPServer");
+}
+
+#[test]
+fn derive_index_lookup() {
+    use doc_for::{doc_for, DocFor};
+
+    /// Some documentation
+    #[derive(DocFor)]
+    #[doc_for(strip = 1)]
+    enum MyEnum {
+        First,
+        Second,
+        /// Third variant
+        Third,
+    }
+
+    #[derive(DocFor)]
+    #[doc_for(strip = 1)]
+    struct MyStruct {
+        /// First field
+        first: i32,
+        second: i32,
+    }
+
+    #[derive(DocFor)]
+    #[doc_for(strip = 1, index_fn = false)]
+    struct MyTupleStruct(
+        /// First field
+        i32,
+    );
+
+    #[derive(DocFor)]
+    struct MyUnitStruct;
+
+    assert_eq!(doc_for!(MyEnum, 2).unwrap(), "Third variant");
+    assert!(doc_for!(MyEnum, 0).is_none());
+    assert_eq!(MyEnum::doc_for_index(2).unwrap(), "Third variant");
+    assert_eq!(MyEnum::COUNT, 3);
+    assert_eq!(doc_for!(MyStruct, 0).unwrap(), "First field");
+    assert!(MyStruct::doc_for_index(1).is_none());
+    assert_eq!(MyStruct::COUNT, 2);
+    assert_eq!(doc_for!(MyTupleStruct, 0).unwrap(), "First field");
+    assert_eq!(MyTupleStruct::doc_for_field(0).unwrap(), "First field");
+    assert_eq!(MyTupleStruct::COUNT, 1);
+    assert_eq!(MyUnitStruct::COUNT, 0);
+}
+
+#[test]
+fn attr_doc_impl_discriminant() {
+    use doc_for::{doc_impl, DocFor};
+
+    #[doc_impl(strip = 1)]
+    #[repr(u16)]
+    enum Code {
+        /// Not found
+        NotFound = 404,
+        /// Internal server error
+        InternalServerError = 500,
+        Undocumented,
+    }
+
+    #[derive(DocFor)]
+    #[doc_for(strip = 1, discriminant_fn = doc_for_code)]
+    #[repr(i8)]
+    enum Signed {
+        /// Negative
+        Negative = -1,
+    }
+
+    const NOT_FOUND: Option<&str> = Code::doc_for_discriminant(404);
+    assert_eq!(NOT_FOUND.unwrap(), "Not found");
+    assert_eq!(
+        Code::try_doc_for_discriminant(500).unwrap().unwrap(),
+        "Internal server error"
+    );
+    assert_eq!(Code::try_doc_for_discriminant(501), Some(None));
+    assert_eq!(Code::try_doc_for_discriminant(200), None);
+    assert_eq!(Signed::doc_for_code(-1).unwrap(), "Negative");
+    assert_eq!(Signed::try_doc_for_code(0), None);
+}
+
+#[test]
+fn derive_from_doc() {
+    use doc_for::{doc_impl, DocDyn, FromDoc};
+
+    #[derive(Debug, PartialEq, Eq, FromDoc, DocDyn)]
+    #[doc_for(strip = 1)]
+    enum Level {
+        /// Low level
+        Low,
+        /// Internal notes
+        #[doc_for(doc = "High level")]
+        High,
+        Undocumented,
+    }
+
+    #[doc_impl(strip = all, doc_for = false, from_doc = true)]
+    #[derive(Debug, PartialEq, Eq)]
+    enum Color {
+        /// Red
+        Red,
+        /// Green
+        Green,
+    }
+
+    assert_eq!(Level::from_doc("Low level"), Some(Level::Low));
+    assert_eq!(Level::from_doc("High level"), Some(Level::High));
+    assert_eq!(Level::from_doc("Internal notes"), None);
+    assert_eq!(Level::from_doc("Undocumented"), None);
+    assert_eq!(
+        Level::from_doc(Level::Low.doc_dyn().unwrap()),
+        Some(Level::Low)
+    );
+    assert_eq!(Color::from_doc("Green"), Some(Color::Green));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_as_doc() {
+    use doc_for::{serde_impls::AsDoc, DocDyn, FromDoc};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Eq, DocDyn, FromDoc)]
+    #[doc_for(strip = 1)]
+    enum Level {
+        /// Low level
+        Low,
+        /// High level
+        High,
+        Undocumented,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Config {
+        #[serde(with = "doc_for::serde_impls")]
+        level: Level,
+        levels: Vec<AsDoc<Level>>,
+    }
+
+    let config = Config {
+        level: Level::High,
+        levels: vec![AsDoc(Level::Low), AsDoc(Level::High)],
+    };
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(
+        json,
+        r#"{"level":"High level","levels":["Low level","High level"]}"#
+    );
+    assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+    assert!(serde_json::to_string(&AsDoc(Level::Undocumented)).is_err());
+    assert!(serde_json::from_str::<AsDoc<Level>>(r#""Unknown""#).is_err());
+}
+
+#[test]
+fn derive_doc_variants() {
+    use doc_for::{DocFor, DocVariants};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, DocFor)]
+    #[doc_for(strip = 1)]
+    enum Format {
+        /// Human-readable output
+        Pretty,
+        Json,
+    }
+
+    assert_eq!(
+        Format::VARIANTS,
+        &[
+            (Format::Pretty, "Pretty", Some("Human-readable output")),
+            (Format::Json, "Json", None),
+        ]
+    );
+}
+
+#[test]
+fn derive_documented() {
+    use doc_for::{DocFor, Documented};
+
+    /// Network settings
+    #[derive(DocFor)]
+    #[doc_for(strip = 1)]
+    struct Network {
+        /// Port to listen on
+        port: u16,
+    }
+
+    /// Log level
+    #[derive(DocFor)]
+    #[doc_for(strip = 1)]
+    enum Level {
+        /// Verbose
+        Debug,
+        Info,
+    }
+
+    /// Raw value
+    #[derive(DocFor)]
+    #[doc_for(strip = 1)]
+    union Raw {
+        int: u32,
+        float: f32,
+    }
+
+    let items: Vec<Box<dyn Documented>> = vec![
+        Box::new(Network { port: 80 }),
+        Box::new(Level::Info),
+        Box::new(Raw { int: 0 }),
+    ];
+    let names: Vec<_> = items.iter().map(|item| item.type_name()).collect();
+    assert_eq!(names, ["Network", "Level", "Raw"]);
+    let docs: Vec<_> = items.iter().map(|item| item.type_doc().unwrap()).collect();
+    assert_eq!(docs, ["Network settings", "Log level", "Raw value"]);
+    assert_eq!(items[0].field_docs()[0].doc.unwrap(), "Port to listen on");
+    assert_eq!(items[1].field_docs()[1].name, "Info");
+    assert_eq!(items[2].field_docs().len(), 2);
+    assert_eq!(Network::NAME, "Network");
+}
+
+#[test]
+fn derive_doc_dyn_delegate() {
+    use doc_for::{DocDyn, DocDynChain};
+
+    #[derive(DocDyn)]
+    #[doc_for(strip = 1)]
+    enum IoError {
+        /// File not found
+        NotFound,
+    }
+
+    #[derive(DocDyn)]
+    #[doc_for(strip = 1)]
+    enum DbError {
+        /// Connection lost
+        ConnectionLost,
+        Undocumented,
+    }
+
+    #[derive(DocDyn)]
+    #[doc_for(strip = 1)]
+    enum AppError {
+        /// I/O error
+        #[doc_for(delegate)]
+        Io(IoError),
+        /// Database error
+        #[doc_for(delegate, fallback)]
+        Db { source: DbError },
+        /// Other error
+        Other(String),
+        /// Timeout
+        Timeout { seconds: u64 },
+    }
+
+    let io = AppError::Io(IoError::NotFound);
+    assert_eq!(io.doc_dyn().unwrap(), "File not found");
+    assert_eq!(io.doc_dyn_variant().unwrap(), "I/O error");
+    assert_eq!(
+        io.doc_dyn_chain().collect::<Vec<_>>(),
+        [Some("I/O error"), Some("File not found")]
+    );
+    let db = AppError::Db {
+        source: DbError::Undocumented,
+    };
+    assert_eq!(db.doc_dyn().unwrap(), "Database error");
+    assert_eq!(
+        DocDynChain::new(&db).collect::<Vec<_>>(),
+        [Some("Database error"), None]
+    );
+    let db = AppError::Db {
+        source: DbError::ConnectionLost,
+    };
+    assert_eq!(db.doc_dyn().unwrap(), "Connection lost");
+    let other = AppError::Other("Something".to_string());
+    assert_eq!(other.doc_dyn().unwrap(), "Other error");
+    assert!(other.doc_dyn_source().is_none());
+    assert_eq!(other.doc_dyn_chain().count(), 1);
+    assert_eq!(
+        AppError::Timeout { seconds: 1 }.doc_dyn().unwrap(),
+        "Timeout"
+    );
+}
+
+#[test]
+fn derive_doc_dyn_fields() {
+    use doc_for::{DocDyn, FieldDoc};
+
+    #[derive(DocDyn)]
+    #[doc_for(strip = 1)]
+    enum Error {
+        /// Invalid input
+        Invalid {
+            /// Line number
+            line: usize,
+            /// Column number
+            #[doc_for(doc = "Column")]
+            column: usize,
+        },
+        /// Timeout
+        Timeout(
+            /// Seconds
+            u64,
+        ),
+        /// Unknown
+        Unknown,
+    }
+
+    let invalid = Error::Invalid { line: 1, column: 2 };
+    assert_eq!(invalid.doc_dyn().unwrap(), "Invalid input");
+    assert_eq!(
+        invalid.doc_dyn_fields(),
+        &[
+            FieldDoc {
+                name: "line",
+                ty: Some("usize"),
+                doc: Some("Line number"),
+            },
+            FieldDoc {
+                name: "column",
+                ty: Some("usize"),
+                doc: Some("Column"),
+            },
+        ]
+    );
+    assert_eq!(Error::Timeout(1).doc_dyn().unwrap(), "Timeout");
+    assert_eq!(Error::Timeout(1).doc_dyn_fields()[0].name, "0");
+    assert_eq!(
+        Error::Timeout(1).doc_dyn_fields()[0].doc.unwrap(),
+        "Seconds"
+    );
+    assert!(Error::Unknown.doc_dyn_fields().is_empty());
+}
+
+#[test]
+fn derive_transparent() {
+    use doc_for::{doc_for, doc_impl, DocFor, Kind};
+
+    /// TLS settings
+    #[derive(DocFor)]
+    #[doc_for(strip = 1)]
+    struct TlsConfig {
+        /// Path to the certificate
+        cert: String,
+    }
+
+    #[derive(DocFor)]
+    #[doc_for(transparent)]
+    struct ValidatedTls(TlsConfig);
+
+    #[derive(DocFor)]
+    #[doc_for(strip = 1)]
+    struct Pair(
+        /// First element
+        i32,
+        i32,
+    );
+
+    #[derive(DocFor)]
+    #[doc_for(transparent)]
+    struct WrappedPair(Pair);
+
+    /// Overridden documentation
+    #[doc_impl(strip = 1, transparent = true)]
+    struct OverriddenTls {
+        inner: TlsConfig,
+    }
+
+    assert_eq!(doc_for!(ValidatedTls).unwrap(), "TLS settings");
+    assert_eq!(
+        doc_for!(ValidatedTls, cert).unwrap(),
+        "Path to the certificate"
+    );
+    assert_eq!(
+        ValidatedTls::doc_for_field("cert").unwrap(),
+        "Path to the certificate"
+    );
+    assert_eq!(
+        ValidatedTls::doc_for_index(0).unwrap(),
+        "Path to the certificate"
+    );
+    assert_eq!(ValidatedTls::NAME, "ValidatedTls");
+    // The kind is forwarded together with the fields
+    assert_eq!(ValidatedTls::KIND, Kind::Struct);
+    assert_eq!(WrappedPair::KIND, Kind::TupleStruct);
+    // Fields of tuple structs are looked up by their names as strings
+    assert_eq!(Pair::doc_for_field(0).unwrap(), "First element");
+    assert_eq!(WrappedPair::doc_for_field("0").unwrap(), "First element");
+    assert_eq!(doc_for!(WrappedPair, 0).unwrap(), "First element");
+    assert_eq!(doc_for!(OverriddenTls).unwrap(), "Overridden documentation");
+    assert_eq!(
+        doc_for!(OverriddenTls, cert).unwrap(),
+        "Path to the certificate"
+    );
+}
+
+#[test]
+fn attr_doc_impl_inherit() {
+    use doc_for::{doc_for, doc_impl, DocFor};
+
+    /// TLS settings
+    #[derive(DocFor)]
+    #[doc_for(strip = 1)]
+    struct TlsConfig {
+        /// Path to the certificate
+        cert: String,
+    }
+
+    #[doc_impl(strip = 1, inherit = field_type, field_enum = true)]
+    struct ServerConfig {
+        tls: TlsConfig,
+        /// Own documentation takes precedence
+        fallback: TlsConfig,
+        port: u16,
+        #[doc_for(skip)]
+        skipped: TlsConfig,
+    }
+
+    #[derive(DocFor)]
+    #[doc_for(inherit = field_type)]
+    struct Pair(TlsConfig, u16);
+
+    assert_eq!(doc_for!(ServerConfig, tls).unwrap(), "TLS settings");
+    assert_eq!(
+        doc_for!(ServerConfig, fallback).unwrap(),
+        "Own documentation takes precedence"
+    );
+    assert!(doc_for!(ServerConfig, port).is_none());
+    assert!(doc_for!(ServerConfig, skipped).is_none());
+    assert_eq!(ServerConfig::doc_for_field("tls").unwrap(), "TLS settings");
+    assert_eq!(ServerConfig::doc_for_index(0).unwrap(), "TLS settings");
+    assert_eq!(ServerConfigField::Tls.doc().unwrap(), "TLS settings");
+    assert_eq!(ServerConfig::FIELDS[0].doc.unwrap(), "TLS settings");
+    assert_eq!(Pair::doc_for_field(0).unwrap(), "TLS settings");
+    assert!(Pair::doc_for_field(1).is_none());
+}
+
+#[test]
+fn derive_wrapper_types() {
+    use doc_for::{doc_for, doc_impl, DocFor};
+    use std::{rc::Rc, sync::Arc};
+
+    /// Routing rule
+    #[derive(DocFor)]
+    #[doc_for(strip = 1)]
+    struct Rule {
+        /// Pattern to match
+        pattern: String,
+    }
+
+    #[doc_impl(strip = 1, inherit = field_type)]
+    struct Config {
+        rule: Option<Rule>,
+        rules: Vec<Rule>,
+        fixed: [Rule; 2],
+        shared: Arc<Rule>,
+        ports: Vec<u16>,
+    }
+
+    assert_eq!(doc_for!(Option<Rule>).unwrap(), "Routing rule");
+    assert_eq!(doc_for!(Box<Rule>).unwrap(), "Routing rule");
+    assert_eq!(doc_for!(Rc<Rule>).unwrap(), "Routing rule");
+    assert_eq!(doc_for!(&'static Rule).unwrap(), "Routing rule");
+    assert_eq!(doc_for!(Vec<Rule>, pattern).unwrap(), "Pattern to match");
+    assert_eq!(<[Rule; 2]>::FIELDS, Rule::FIELDS);
+    assert_eq!(<Vec<Option<Rule>>>::NAME, "Rule");
+    assert_eq!(doc_for!(Config, rule).unwrap(), "Routing rule");
+    assert_eq!(doc_for!(Config, rules).unwrap(), "Routing rule");
+    assert_eq!(doc_for!(Config, fixed).unwrap(), "Routing rule");
+    assert_eq!(doc_for!(Config, shared).unwrap(), "Routing rule");
+    assert!(doc_for!(Config, ports).is_none());
+}
+
+#[test]
+fn derive_remote() {
+    use doc_for::{doc_for, doc_impl, DocFor, DocVariants};
+
+    doc_for::remote! {
+        std::time::Duration =>
+        /// A span of time
+        #[doc_for(strip = 1)]
+        struct DurationDef {
+            /// Whole seconds
+            secs: u64,
+            nanos: u32,
+        }
+    }
+
+    doc_for::remote! {
+        std::cmp::Ordering =>
+        #[doc_for(strip = 1)]
+        enum OrderingDef {
+            /// Less than
+            Less,
+            /// Equal to
+            Equal,
+            /// Greater than
+            Greater,
+        }
+    }
+
+    /// Mirror declared without the macro
+    #[doc_impl(strip = 1, remote = "std::net::Ipv4Addr")]
+    struct Ipv4AddrDef;
+
+    #[doc_impl(strip = 1, inherit = field_type)]
+    struct Config {
+        #[doc_for(remote = DurationDef)]
+        timeout: std::time::Duration,
+        /// Own documentation takes precedence
+        #[doc_for(remote = DurationDef)]
+        interval: std::time::Duration,
+        #[doc_for(remote = Ipv4AddrDef)]
+        address: std::net::Ipv4Addr,
+        retry: Option<std::time::Duration>,
+    }
+
+    assert_eq!(DurationDef::NAME, "Duration");
+    assert_eq!(doc_for!(DurationDef).unwrap(), "A span of time");
+    assert_eq!(doc_for!(DurationDef, secs).unwrap(), "Whole seconds");
+    assert!(doc_for!(DurationDef, nanos).is_none());
+    assert_eq!(OrderingDef::NAME, "Ordering");
+    assert_eq!(OrderingDef::VARIANTS[2].2.unwrap(), "Greater than");
+    assert_eq!(Ipv4AddrDef::NAME, "Ipv4Addr");
+    assert_eq!(doc_for!(Config, timeout).unwrap(), "A span of time");
+    assert_eq!(
+        doc_for!(Config, interval).unwrap(),
+        "Own documentation takes precedence"
+    );
+    assert_eq!(
+        doc_for!(Config, address).unwrap(),
+        "Mirror declared without the macro"
+    );
+    assert!(doc_for!(Config, retry).is_none());
+}
+
+#[test]
+fn derive_structural_metadata() {
+    use doc_for::{doc_impl, DocFor, Kind};
+
+    /// Some documentation
+    #[doc_impl(strip = 1)]
+    struct MyStruct {
+        /// Field documentation
+        field: Option<Vec<u16>>,
+        reference: &'static str,
+        array: [u8; 4],
+    }
+
+    #[doc_impl]
+    struct MyTupleStruct(i32, (u8, bool));
+
+    #[doc_impl]
+    struct MyUnit;
+
+    #[doc_impl]
+    enum MyEnum {
+        Variant,
+    }
+
+    #[doc_impl]
+    union MyUnion {
+        integer: u32,
+        float: f32,
+    }
+
+    assert_eq!(MyStruct::KIND, Kind::Struct);
+    assert_eq!(MyTupleStruct::KIND, Kind::TupleStruct);
+    assert_eq!(MyUnit::KIND, Kind::Unit);
+    assert_eq!(MyEnum::KIND, Kind::Enum);
+    assert_eq!(MyUnion::KIND, Kind::Union);
+    assert_eq!(<Option<MyEnum>>::KIND, Kind::Enum);
+
+    let rows: Vec<_> = MyStruct::FIELDS
+        .iter()
+        .map(|f| {
+            format!(
+                "{}: {} - {}",
+                f.name,
+                f.ty.unwrap(),
+                f.doc.unwrap_or_default()
+            )
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        [
+            "field: Option<Vec<u16>> - Field documentation",
+            "reference: &'static str - ",
+            "array: [u8; 4] - ",
+        ]
+    );
+    assert_eq!(MyStruct::COUNT, 3);
+    assert_eq!(MyTupleStruct::FIELDS[1].ty.unwrap(), "(u8, bool)");
+    assert_eq!(MyUnit::COUNT, 0);
+    assert_eq!(MyEnum::FIELDS[0].ty, None);
+    assert_eq!(MyUnion::FIELDS[1].ty.unwrap(), "f32");
+}
+
+#[test]
+fn derive_doc_tree() {
+    use doc_for::{doc_impl, DocFor, DocTree, Kind};
+
+    doc_for::remote! {
+        std::time::Duration =>
+        /// A span of time
+        #[doc_for(strip = 1)]
+        struct DurationDef {
+            secs: u64,
+            nanos: u32,
+        }
+    }
+
+    /// A routing rule
+    #[doc_impl(strip = 1)]
+    struct Rule {
+        /// Pattern to match
+        pattern: String,
+        /// Nested rules
+        children: Vec<Rule>,
+    }
+
+    /// Where to write logs
+    #[doc_impl(strip = 1)]
+    enum Output {
+        /// Standard output
+        Stdout,
+        /// A file
+        File {
+            /// Path to the file
+            path: String,
+            /// Rotation interval
+            #[doc_for(remote = DurationDef)]
+            rotate: std::time::Duration,
+        },
+    }
+
+    /// Server settings
+    #[derive(DocFor)]
+    #[doc_for(strip = 1)]
+    struct Server {
+        /// Port to listen on
+        port: u16,
+        /// Routing rules
+        rules: Vec<Rule>,
+        /// Log output
+        output: Option<Box<Output>>,
+    }
+
+    #[derive(DocFor)]
+    #[doc_for(transparent)]
+    struct Wrapped(Server);
+
+    let tree = DocTree::of::<Server>();
+    assert_eq!(tree.name, "Server");
+    assert_eq!(tree.kind, Kind::Struct);
+    assert_eq!(tree.doc.unwrap(), "Server settings");
+    assert_eq!(tree.fields.len(), 3);
+
+    let port = &tree.fields[0];
+    assert_eq!(port.ty.unwrap(), "u16");
+    assert!(port.tree.is_none());
+
+    let rules = tree.fields[1].tree.as_ref().unwrap();
+    assert_eq!(rules.name, "Rule");
+    assert_eq!(rules.fields[0].doc.unwrap(), "Pattern to match");
+    // Recursion stops at the ancestors
+    assert!(rules.fields[1].tree.is_none());
+
+    let output = tree.fields[2].tree.as_ref().unwrap();
+    assert_eq!(output.kind, Kind::Enum);
+    assert_eq!(output.fields[0].name, "Stdout");
+    assert!(output.fields[0].fields.is_empty());
+    let file = &output.fields[1];
+    assert_eq!(file.ty, None);
+    assert_eq!(file.fields[0].doc.unwrap(), "Path to the file");
+    let rotate = file.fields[1].tree.as_ref().unwrap();
+    assert_eq!(rotate.name, "Duration");
+    assert_eq!(rotate.doc.unwrap(), "A span of time");
+
+    let wrapped = DocTree::of::<Wrapped>();
+    assert_eq!(wrapped.name, "Wrapped");
+    assert_eq!(wrapped.kind, Kind::Struct);
+    assert_eq!(wrapped.fields, tree.fields);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_glob_import() {
+    // The module of adapters doesn't shadow the `serde` crate
+    use doc_for::*;
+    use serde::Serialize;
+
+    /// Glob imported
+    #[derive(Serialize, DocFor)]
+    #[doc_for(strip = 1)]
+    struct MyStruct {
+        field: i32,
+    }
+
+    assert_eq!(doc_for!(MyStruct).unwrap(), "Glob imported");
+    assert_eq!(
+        serde_json::to_string(&MyStruct { field: 1 }).unwrap(),
+        r#"{"field":1}"#
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_doc_tree() {
+    use doc_for::{doc_impl, DocTree};
+
+    /// TLS settings
+    #[doc_impl(strip = 1)]
+    struct Tls {
+        /// Path to the certificate
+        cert: String,
+    }
+
+    /// Server settings
+    #[doc_impl(strip = 1)]
+    struct Server {
+        port: u16,
+        tls: Option<Tls>,
+    }
+
+    let json = serde_json::to_value(DocTree::of::<Server>()).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "name": "Server",
+            "kind": "Struct",
+            "doc": "Server settings",
+            "fields": [
+                { "name": "port", "ty": "u16", "doc": null, "fields": [], "tree": null },
+                {
+                    "name": "tls",
+                    "ty": "Option<Tls>",
+                    "doc": null,
+                    "fields": [],
+                    "tree": {
+                        "name": "Tls",
+                        "kind": "Struct",
+                        "doc": "TLS settings",
+                        "fields": [
+                            {
+                                "name": "cert",
+                                "ty": "String",
+                                "doc": "Path to the certificate",
+                                "fields": [],
+                                "tree": null,
+                            },
+                        ],
+                    },
+                },
+            ],
+        })
+    );
+}
+
+#[test]
+fn derive_serde_names() {
+    use doc_for::{doc_for, doc_impl, field_doc_by_name, DocDyn, DocFor, DocTree};
+    use serde::Deserialize;
+
+    /// Log settings
+    #[derive(Deserialize, DocFor)]
+    #[doc_for(strip = 1, serde_names)]
+    #[serde(rename_all = "kebab-case")]
+    struct Log {
+        /// Minimum level
+        log_level: String,
+    }
+
+    /// Server settings
+    #[doc_impl(strip = 1, serde_names = true)]
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Server {
+        /// Port to listen on
+        listen_port: u16,
+        /// Host name
+        #[serde(rename(serialize = "hostname", deserialize = "host"))]
+        host_name: String,
+        /// Not configurable
+        #[serde(skip)]
+        #[allow(dead_code)]
+        cache: Vec<u8>,
+        #[serde(flatten)]
+        log: Log,
+        /// Maximum connections
+        max_connections: u32,
+    }
+
+    #[derive(Deserialize, DocDyn)]
+    #[doc_for(strip = 1, serde_names)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE", rename_all_fields = "PascalCase")]
+    #[allow(dead_code)]
+    enum Output {
+        /// Standard output
+        StdOut,
+        /// A file
+        LogFile {
+            /// Path to the file
+            file_path: String,
+        },
+    }
+
+    let names: Vec<_> = Server::FIELDS.iter().map(|field| field.name).collect();
+    assert_eq!(names, ["listenPort", "host", "log-level", "maxConnections"]);
+    // The same names are accepted by serde
+    let server: Server = serde_json::from_str(
+        r#"{"listenPort":80,"host":"localhost","log-level":"info","maxConnections":8}"#,
+    )
+    .unwrap();
+    assert_eq!(server.log.log_level, "info");
+
+    assert_eq!(doc_for!(Server, listenPort).unwrap(), "Port to listen on");
+    assert_eq!(
+        field_doc_by_name::<Server>("log-level").unwrap(),
+        "Minimum level"
+    );
+    assert_eq!(
+        Server::doc_for_field("maxConnections").unwrap(),
+        "Maximum connections"
+    );
+    assert_eq!(Server::doc_for_index(2).unwrap(), "Minimum level");
+    assert_eq!(Server::COUNT, 4);
+
+    let tree = DocTree::of::<Server>();
+    let names: Vec<_> = tree.fields.iter().map(|field| field.name).collect();
+    assert_eq!(names, ["listenPort", "host", "log-level", "maxConnections"]);
+
+    let file = Output::LogFile {
+        file_path: String::new(),
+    };
+    assert_eq!(file.doc_dyn_fields()[0].name, "FilePath");
+    assert_eq!(Output::StdOut.doc_dyn().unwrap(), "Standard output");
+}
+
+#[test]
+fn derive_serde_names_variants() {
+    use doc_for::{DocFor, DocTree};
+    use serde::Deserialize;
+
+    #[derive(Deserialize, DocFor)]
+    #[doc_for(strip = 1, serde_names)]
+    #[serde(rename_all = "snake_case")]
+    #[allow(dead_code)]
+    enum Mode {
+        /// Run in the foreground
+        Foreground,
+        /// Run as a daemon
+        RunAsDaemon {
+            /// Path to the PID file
+            #[serde(rename = "pid")]
+            pid_file: String,
+        },
+        #[serde(skip)]
+        Internal,
+    }
+
+    let names: Vec<_> = Mode::FIELDS.iter().map(|field| field.name).collect();
+    assert_eq!(names, ["foreground", "run_as_daemon"]);
+    assert_eq!(
+        Mode::doc_for_field("run_as_daemon").unwrap(),
+        "Run as a daemon"
+    );
+    assert!(serde_json::from_str::<Mode>(r#""foreground""#).is_ok());
+    let tree = DocTree::of::<Mode>();
+    assert_eq!(tree.fields[1].fields[0].name, "pid");
+}